@@ -0,0 +1,220 @@
+//! Writers for saving meshes extracted from SDFs in common file formats.
+//!
+//! Every writer accepts any `std::io::Write`. Wrapping files in a `std::io::BufWriter` is
+//! recommended, since the writers issue many small writes.
+use crate::mathtypes::{Real, Vec3};
+use crate::meshing::Mesh;
+use std::io::{self, Write};
+
+/// Iterates over the faces of `mesh` as triangles, splitting quads in two.
+fn triangles<V>(mesh: &Mesh<V>) -> impl Iterator<Item = [u32; 3]> + '_ {
+    mesh.triangles.iter().copied().chain(
+        mesh.quads
+            .iter()
+            .flat_map(|&[a, b, c, d]| [[a, b, c], [a, c, d]]),
+    )
+}
+
+#[inline]
+fn components<T: Real, V: Vec3<T>>(v: V) -> [f64; 3] {
+    [v.x().to_f64(), v.y().to_f64(), v.z().to_f64()]
+}
+
+/// The unit normal of a triangle wound counter-clockwise.
+fn face_normal<T: Real, V: Vec3<T>>(mesh: &Mesh<V>, [a, b, c]: [u32; 3]) -> [f64; 3] {
+    let [ax, ay, az] = components(mesh.vertices[a as usize]);
+    let [bx, by, bz] = components(mesh.vertices[b as usize]);
+    let [cx, cy, cz] = components(mesh.vertices[c as usize]);
+    let (ux, uy, uz) = (bx - ax, by - ay, bz - az);
+    let (vx, vy, vz) = (cx - ax, cy - ay, cz - az);
+    let n = [uy * vz - uz * vy, uz * vx - ux * vz, ux * vy - uy * vx];
+    let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if len > 0.0 {
+        [n[0] / len, n[1] / len, n[2] / len]
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// Writes `mesh` as a Wavefront OBJ file, including vertex normals if the mesh has them.
+/// Quads are written as four-sided faces.
+pub fn write_obj<T, V, W>(mesh: &Mesh<V>, mut writer: W) -> io::Result<()>
+where
+    T: Real,
+    V: Vec3<T>,
+    W: Write,
+{
+    for &v in mesh.vertices.iter() {
+        let [x, y, z] = components(v);
+        writeln!(writer, "v {} {} {}", x, y, z)?;
+    }
+    if let Some(normals) = &mesh.normals {
+        for &n in normals.iter() {
+            let [x, y, z] = components(n);
+            writeln!(writer, "vn {} {} {}", x, y, z)?;
+        }
+    }
+
+    let has_normals = mesh.normals.is_some();
+    let faces = mesh
+        .triangles
+        .iter()
+        .map(|t| &t[..])
+        .chain(mesh.quads.iter().map(|q| &q[..]));
+    for face in faces {
+        write!(writer, "f")?;
+        for &i in face {
+            if has_normals {
+                write!(writer, " {}//{}", i + 1, i + 1)?;
+            } else {
+                write!(writer, " {}", i + 1)?;
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes `mesh` as an ASCII STL file named `name`. Quads are split into triangles, and
+/// facet normals are computed from the winding of each triangle.
+pub fn write_stl_ascii<T, V, W>(mesh: &Mesh<V>, name: &str, mut writer: W) -> io::Result<()>
+where
+    T: Real,
+    V: Vec3<T>,
+    W: Write,
+{
+    writeln!(writer, "solid {}", name)?;
+    for triangle in triangles(mesh) {
+        let [nx, ny, nz] = face_normal(mesh, triangle);
+        writeln!(writer, "facet normal {} {} {}", nx, ny, nz)?;
+        writeln!(writer, "outer loop")?;
+        for &i in triangle.iter() {
+            let [x, y, z] = components(mesh.vertices[i as usize]);
+            writeln!(writer, "vertex {} {} {}", x, y, z)?;
+        }
+        writeln!(writer, "endloop")?;
+        writeln!(writer, "endfacet")?;
+    }
+    writeln!(writer, "endsolid {}", name)?;
+    Ok(())
+}
+
+/// Writes `mesh` as a binary STL file. Quads are split into triangles, and facet normals
+/// are computed from the winding of each triangle.
+///
+/// Returns an error of kind `InvalidInput` if the mesh has more triangles than fit in an STL
+/// file.
+pub fn write_stl_binary<T, V, W>(mesh: &Mesh<V>, mut writer: W) -> io::Result<()>
+where
+    T: Real,
+    V: Vec3<T>,
+    W: Write,
+{
+    let count = mesh.triangles.len() + 2 * mesh.quads.len();
+    if count > u32::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "too many triangles for an STL file",
+        ));
+    }
+
+    let mut header = [0u8; 80];
+    let label = b"binary STL written by sdfu";
+    header[..label.len()].copy_from_slice(label);
+    writer.write_all(&header)?;
+    writer.write_all(&(count as u32).to_le_bytes())?;
+
+    for triangle in triangles(mesh) {
+        for c in face_normal(mesh, triangle).iter() {
+            writer.write_all(&(*c as f32).to_le_bytes())?;
+        }
+        for &i in triangle.iter() {
+            for c in components(mesh.vertices[i as usize]).iter() {
+                writer.write_all(&(*c as f32).to_le_bytes())?;
+            }
+        }
+        writer.write_all(&0u16.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_ply_header<V, W: Write>(mesh: &Mesh<V>, format: &str, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {} 1.0", format)?;
+    writeln!(writer, "element vertex {}", mesh.vertices.len())?;
+    writeln!(writer, "property float x")?;
+    writeln!(writer, "property float y")?;
+    writeln!(writer, "property float z")?;
+    if mesh.normals.is_some() {
+        writeln!(writer, "property float nx")?;
+        writeln!(writer, "property float ny")?;
+        writeln!(writer, "property float nz")?;
+    }
+    writeln!(
+        writer,
+        "element face {}",
+        mesh.triangles.len() + mesh.quads.len()
+    )?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "end_header")
+}
+
+/// Writes `mesh` as an ASCII PLY file, including vertex normals if the mesh has them.
+/// Quads are written as four-sided faces.
+pub fn write_ply_ascii<T, V, W>(mesh: &Mesh<V>, mut writer: W) -> io::Result<()>
+where
+    T: Real,
+    V: Vec3<T>,
+    W: Write,
+{
+    write_ply_header(mesh, "ascii", &mut writer)?;
+    for (i, &v) in mesh.vertices.iter().enumerate() {
+        let [x, y, z] = components(v);
+        write!(writer, "{} {} {}", x, y, z)?;
+        if let Some(normals) = &mesh.normals {
+            let [nx, ny, nz] = components(normals[i]);
+            write!(writer, " {} {} {}", nx, ny, nz)?;
+        }
+        writeln!(writer)?;
+    }
+    for [a, b, c] in mesh.triangles.iter() {
+        writeln!(writer, "3 {} {} {}", a, b, c)?;
+    }
+    for [a, b, c, d] in mesh.quads.iter() {
+        writeln!(writer, "4 {} {} {} {}", a, b, c, d)?;
+    }
+    Ok(())
+}
+
+/// Writes `mesh` as a binary little-endian PLY file, including vertex normals if the mesh
+/// has them. Quads are written as four-sided faces.
+pub fn write_ply_binary<T, V, W>(mesh: &Mesh<V>, mut writer: W) -> io::Result<()>
+where
+    T: Real,
+    V: Vec3<T>,
+    W: Write,
+{
+    write_ply_header(mesh, "binary_little_endian", &mut writer)?;
+    for (i, &v) in mesh.vertices.iter().enumerate() {
+        for c in components(v).iter() {
+            writer.write_all(&(*c as f32).to_le_bytes())?;
+        }
+        if let Some(normals) = &mesh.normals {
+            for c in components(normals[i]).iter() {
+                writer.write_all(&(*c as f32).to_le_bytes())?;
+            }
+        }
+    }
+    let faces = mesh
+        .triangles
+        .iter()
+        .map(|t| &t[..])
+        .chain(mesh.quads.iter().map(|q| &q[..]));
+    for face in faces {
+        writer.write_all(&[face.len() as u8])?;
+        for &i in face {
+            writer.write_all(&(i as i32).to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,373 @@
+//! SDFs baked into grids of samples, which are much cheaper to evaluate than a large tree
+//! of primitives and operations.
+//!
+//! `Grid3` stores samples at every point of a regular grid, while `Octree` only refines the
+//! cells close to the surface, which takes far less memory at high resolutions.
+use crate::mathtypes::{Real, Vec3};
+use crate::meshing::Samples;
+use crate::SDF;
+
+/// How a `Grid3` interpolates between its samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Linear interpolation between the eight samples surrounding a point. This is fast and
+    /// never overshoots the samples, but its gradient is discontinuous across cells.
+    Trilinear,
+    /// Catmull-Rom interpolation over the 64 samples surrounding a point, which gives smoother
+    /// surfaces and normals at the cost of being slower.
+    Tricubic,
+}
+
+/// A dense grid of distances sampled from an SDF over an axis-aligned box.
+///
+/// Inside the box, the distance is interpolated from the samples. Outside of it, the
+/// distance to the box is combined with the distance sampled at the closest point on it,
+/// which is a lower bound on the true distance as long as the whole surface lies inside the
+/// box. This means a grid can safely be used in operations like `Union` and `Subtraction`.
+///
+/// Since the grid owns its samples, it does not implement `SDF` itself; instead, a
+/// reference to it does.
+#[derive(Clone, Debug)]
+pub struct Grid3<T, V> {
+    pub min: V,
+    pub max: V,
+    /// The number of cells along each axis. There is one more sample than cells along
+    /// each axis.
+    pub resolution: [usize; 3],
+    /// The sampled distances, ordered by x, then y, then z.
+    pub values: Vec<T>,
+    pub interpolation: Interpolation,
+}
+
+impl<T: Real, V: Vec3<T>> Grid3<T, V> {
+    /// Bakes `sdf` into a grid spanning from `min` to `max`, with `resolution` cells along
+    /// each axis. Every entry of `resolution` must be at least one.
+    pub fn bake<S: SDF<T, V>>(
+        sdf: S,
+        min: V,
+        max: V,
+        resolution: [usize; 3],
+        interpolation: Interpolation,
+    ) -> Self {
+        assert!(
+            resolution.iter().all(|&n| n > 0),
+            "Grid3 resolution must be at least one cell along each axis"
+        );
+        let samples = Samples::new(sdf, min, max, resolution);
+        Grid3 {
+            min,
+            max,
+            resolution,
+            values: samples.values,
+            interpolation,
+        }
+    }
+
+    /// The sample at the given grid coordinates, clamped to the grid.
+    #[inline]
+    pub fn value(&self, x: usize, y: usize, z: usize) -> T {
+        let [nx, ny, nz] = self.resolution;
+        let (x, y, z) = (x.min(nx), y.min(ny), z.min(nz));
+        self.values[x + (nx + 1) * (y + (ny + 1) * z)]
+    }
+
+    /// Finds the cell containing `value` along one axis and the position within it.
+    #[inline]
+    fn cell(value: T, min: T, max: T, resolution: usize) -> (usize, f64) {
+        let g = ((value - min) / (max - min)).to_f64() * resolution as f64;
+        let i = (g.floor().max(0.0) as usize).min(resolution - 1);
+        (i, g - i as f64)
+    }
+
+    fn trilinear(&self, [x, y, z]: [usize; 3], [fx, fy, fz]: [f64; 3]) -> T {
+        let lerp = |a: T, b: T, t: f64| a + (b - a) * T::from_f64(t);
+        let plane = |z| {
+            let row = |y| lerp(self.value(x, y, z), self.value(x + 1, y, z), fx);
+            lerp(row(y), row(y + 1), fy)
+        };
+        lerp(plane(z), plane(z + 1), fz)
+    }
+
+    fn tricubic(&self, [x, y, z]: [usize; 3], [fx, fy, fz]: [f64; 3]) -> T {
+        // Samples beyond the edges of the grid are clamped to the edge.
+        let offset = |i: usize, k: usize| (i + k).saturating_sub(1);
+        let (wx, wy, wz) = (
+            catmull_rom::<T>(fx),
+            catmull_rom::<T>(fy),
+            catmull_rom::<T>(fz),
+        );
+        let mut sum = T::zero();
+        for (k, &wz) in wz.iter().enumerate() {
+            for (j, &wy) in wy.iter().enumerate() {
+                let mut row = T::zero();
+                for (i, &wx) in wx.iter().enumerate() {
+                    row = row + self.value(offset(x, i), offset(y, j), offset(z, k)) * wx;
+                }
+                sum = sum + row * wy * wz;
+            }
+        }
+        sum
+    }
+
+    /// Interpolates the samples at `p`, which must lie inside the grid.
+    fn sample(&self, p: V) -> T {
+        let (x, fx) = Self::cell(p.x(), self.min.x(), self.max.x(), self.resolution[0]);
+        let (y, fy) = Self::cell(p.y(), self.min.y(), self.max.y(), self.resolution[1]);
+        let (z, fz) = Self::cell(p.z(), self.min.z(), self.max.z(), self.resolution[2]);
+        match self.interpolation {
+            Interpolation::Trilinear => self.trilinear([x, y, z], [fx, fy, fz]),
+            Interpolation::Tricubic => self.tricubic([x, y, z], [fx, fy, fz]),
+        }
+    }
+}
+
+/// The Catmull-Rom weights of the four samples around a point at `t` between the middle two.
+#[inline]
+fn catmull_rom<T: Real>(t: f64) -> [T; 4] {
+    let (t2, t3) = (t * t, t * t * t);
+    [
+        0.5 * (-t3 + 2.0 * t2 - t),
+        0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+        0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+        0.5 * (t3 - t2),
+    ]
+    .map(T::from_f64)
+}
+
+impl<T, V> SDF<T, V> for &Grid3<T, V>
+where
+    T: Real,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        bounded_dist(p, self.min, self.max, |q| self.sample(q))
+    }
+}
+
+/// The distance at `p` of a baked SDF which is only known inside the box from `min` to
+/// `max`, where `inside` gives the distance at points in the box. Outside of the box, the
+/// distance is a lower bound found from the closest point on the box.
+#[inline]
+fn bounded_dist<T, V, F>(p: V, min: V, max: V, inside: F) -> T
+where
+    T: Real,
+    V: Vec3<T>,
+    F: FnOnce(V) -> T,
+{
+    let q = p.max(min).min(max);
+    let outside = (p - q).magnitude();
+    let inside = inside(q);
+    if outside > T::zero() {
+        outside.max(inside - outside)
+    } else {
+        inside
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum OctreeNode<T> {
+    /// A cell far enough from the surface that it lies entirely on one side of it, storing
+    /// the distance at its center.
+    Far(T),
+    /// A cell close to the surface, storing the distances at its corners.
+    Leaf([T; 8]),
+    /// A subdivided cell, storing the index of the first of its eight children.
+    Branch(usize),
+}
+
+/// A sparse, adaptively refined grid of distances sampled from an SDF over an
+/// axis-aligned box.
+///
+/// Cells are only subdivided where the distance at their center is less than the length of
+/// their diagonal, so that only a narrow band around the surface is stored at full
+/// resolution. Cells close to the surface interpolate the distances at their corners
+/// trilinearly. Cells further away only store the distance at their center, and return a
+/// conservative distance that shrinks with the distance from the center, so that the result
+/// never overestimates the distance to the surface by more than the error of the
+/// interpolated cells. Outside of the box, distances are bounded like those of a `Grid3`.
+///
+/// Since the octree owns its nodes, it does not implement `SDF` itself; instead, a
+/// reference to it does.
+#[derive(Clone, Debug)]
+pub struct Octree<T, V> {
+    min: V,
+    max: V,
+    nodes: Vec<OctreeNode<T>>,
+}
+
+impl<T: Real, V: Vec3<T>> Octree<T, V> {
+    /// Bakes `sdf` into an octree spanning from `min` to `max`, subdividing cells near the
+    /// surface at most `max_depth` times.
+    ///
+    /// If `tolerance` is given, cells near the surface also stop being subdivided once
+    /// interpolating their corners reproduces the SDF at the center of the cell and its faces
+    /// to within `tolerance`. This saves a lot of memory around flat parts of the surface.
+    pub fn bake<S: SDF<T, V>>(
+        sdf: S,
+        min: V,
+        max: V,
+        max_depth: usize,
+        tolerance: Option<T>,
+    ) -> Self {
+        let mut octree = Octree {
+            min,
+            max,
+            nodes: vec![OctreeNode::Far(T::zero())],
+        };
+        octree.nodes[0] = octree.build(sdf, min, max, max_depth, tolerance);
+        octree
+    }
+
+    /// The number of nodes in the octree.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The lower and upper corners of child `i` of the cell spanning from `min` to `max`.
+    /// Children are indexed like the corners of a cell, with the x, y and z axes in the
+    /// first, second and third bit.
+    #[inline]
+    fn child(min: V, max: V, i: usize) -> (V, V) {
+        let mid = (min + max) * T::point_five();
+        let split = |bit: usize, lo: T, mid: T, hi: T| {
+            if i & bit == 0 {
+                (lo, mid)
+            } else {
+                (mid, hi)
+            }
+        };
+        let (x0, x1) = split(1, min.x(), mid.x(), max.x());
+        let (y0, y1) = split(2, min.y(), mid.y(), max.y());
+        let (z0, z1) = split(4, min.z(), mid.z(), max.z());
+        (V::new(x0, y0, z0), V::new(x1, y1, z1))
+    }
+
+    /// The point at `t` between `min` and `max` along each axis.
+    #[inline]
+    fn lerp(min: V, max: V, [tx, ty, tz]: [f64; 3]) -> V {
+        let size = max - min;
+        V::new(
+            min.x() + size.x() * T::from_f64(tx),
+            min.y() + size.y() * T::from_f64(ty),
+            min.z() + size.z() * T::from_f64(tz),
+        )
+    }
+
+    fn build<S: SDF<T, V>>(
+        &mut self,
+        sdf: S,
+        min: V,
+        max: V,
+        depth: usize,
+        tolerance: Option<T>,
+    ) -> OctreeNode<T> {
+        let center = sdf.dist((min + max) * T::point_five());
+        let diagonal = (max - min).magnitude();
+        if center.max(-center) >= diagonal {
+            return OctreeNode::Far(center);
+        }
+
+        let mut corners = [T::zero(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let t = [(i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64];
+            *corner = sdf.dist(Self::lerp(min, max, t));
+        }
+        let accurate = || {
+            tolerance.map_or(false, |tolerance| {
+                let probes = [
+                    [0.5, 0.5, 0.5],
+                    [0.0, 0.5, 0.5],
+                    [1.0, 0.5, 0.5],
+                    [0.5, 0.0, 0.5],
+                    [0.5, 1.0, 0.5],
+                    [0.5, 0.5, 0.0],
+                    [0.5, 0.5, 1.0],
+                ];
+                probes.iter().all(|&t| {
+                    let error = trilinear(&corners, t) - sdf.dist(Self::lerp(min, max, t));
+                    error.max(-error) <= tolerance
+                })
+            })
+        };
+        if depth == 0 || accurate() {
+            return OctreeNode::Leaf(corners);
+        }
+
+        let first = self.nodes.len();
+        self.nodes
+            .extend(std::iter::repeat(OctreeNode::Far(T::zero())).take(8));
+        for i in 0..8 {
+            let (child_min, child_max) = Self::child(min, max, i);
+            self.nodes[first + i] = self.build(sdf, child_min, child_max, depth - 1, tolerance);
+        }
+        OctreeNode::Branch(first)
+    }
+
+    /// Finds the distance at `p`, which must lie inside the octree.
+    fn sample(&self, p: V) -> T {
+        let (mut min, mut max) = (self.min, self.max);
+        let mut node = self.nodes[0];
+        loop {
+            match node {
+                OctreeNode::Far(center) => {
+                    let offset = (p - (min + max) * T::point_five()).magnitude();
+                    return if center < T::zero() {
+                        center + offset
+                    } else {
+                        center - offset
+                    };
+                }
+                OctreeNode::Leaf(corners) => {
+                    let size = max - min;
+                    let fraction = |p: T, min: T, size: T| {
+                        if size > T::zero() {
+                            ((p - min) / size).to_f64()
+                        } else {
+                            0.0
+                        }
+                    };
+                    let t = [
+                        fraction(p.x(), min.x(), size.x()),
+                        fraction(p.y(), min.y(), size.y()),
+                        fraction(p.z(), min.z(), size.z()),
+                    ];
+                    return trilinear(&corners, t);
+                }
+                OctreeNode::Branch(first) => {
+                    let mid = (min + max) * T::point_five();
+                    let i = (p.x() >= mid.x()) as usize
+                        | ((p.y() >= mid.y()) as usize) << 1
+                        | ((p.z() >= mid.z()) as usize) << 2;
+                    let (child_min, child_max) = Self::child(min, max, i);
+                    min = child_min;
+                    max = child_max;
+                    node = self.nodes[first + i];
+                }
+            }
+        }
+    }
+}
+
+/// Interpolates the values at the corners of a cell, indexed like the children of an
+/// `Octree` node, at the fraction `t` of the way across it along each axis.
+#[inline]
+fn trilinear<T: Real>(corners: &[T; 8], [tx, ty, tz]: [f64; 3]) -> T {
+    let lerp = |a: T, b: T, t: f64| a + (b - a) * T::from_f64(t);
+    let face = |z: usize| {
+        let edge = |y: usize| lerp(corners[4 * z + 2 * y], corners[4 * z + 2 * y + 1], tx);
+        lerp(edge(0), edge(1), ty)
+    };
+    lerp(face(0), face(1), tz)
+}
+
+impl<T, V> SDF<T, V> for &Octree<T, V>
+where
+    T: Real,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        bounded_dist(p, self.min, self.max, |q| self.sample(q))
+    }
+}
@@ -67,6 +67,14 @@ pub mod ops;
 use ops::*;
 pub mod mods;
 use mods::*;
+pub mod export;
+pub mod grid;
+pub mod meshing;
+pub mod polygon;
+pub mod raymarch;
+pub mod sweep;
+use sweep::{Sweep, SweepPath};
+pub mod trimesh;
 
 /// The core trait of this crate; an implementor of this trait is able
 /// to take in a vector and return the min distance from that vector to
@@ -163,6 +171,30 @@ pub trait SDF<T, V: Vec<T>>: Copy {
         ElongateMulti::new(self, elongation)
     }
 
+    /// Extrude this 2D SDF into a 3D solid along an axis, extending `half_height` to either
+    /// side of the origin.
+    fn extrude(self, axis: Axis, half_height: T) -> Extrude<T, Self> {
+        Extrude::new(self, axis, half_height)
+    }
+
+    /// Revolve this 2D SDF around an axis into a 3D solid, with its Y axis along the axis of
+    /// revolution and its origin `offset` away from it.
+    fn revolve(self, axis: Axis, offset: T) -> Revolve<T, Self> {
+        Revolve::new(self, axis, offset)
+    }
+
+    /// Revolve this 2D SDF around an axis like `revolve`, but only between the angles
+    /// `start` and `end`, in radians.
+    fn revolve_partial(self, axis: Axis, offset: T, start: T, end: T) -> RevolvePartial<T, Self> {
+        RevolvePartial::new(self, axis, offset, start, end)
+    }
+
+    /// Sweep this 2D SDF along a path into a 3D solid, keeping the profile's orientation
+    /// relative to the path as it bends.
+    fn sweep<P>(self, path: &SweepPath<T, P>) -> Sweep<'_, Self, T, P> {
+        Sweep::new(self, path)
+    }
+
     /// Translate the SDF by a vector.
     fn translate(self, translation: V) -> Translate<V, Self> {
         Translate::new(self, translation)
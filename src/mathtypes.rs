@@ -37,6 +37,19 @@ pub trait Vec3<T>: Vec<T> {
     fn x(&self) -> T;
     fn y(&self) -> T;
     fn z(&self) -> T;
+
+    /// The cross product of `self` and `other`.
+    #[inline]
+    fn cross(&self, other: Self) -> Self
+    where
+        T: Mul<T, Output = T> + Sub<T, Output = T>,
+    {
+        Self::new(
+            self.y() * other.z() - self.z() * other.y(),
+            self.z() * other.x() - self.x() * other.z(),
+            self.x() * other.y() - self.y() * other.x(),
+        )
+    }
 }
 
 /// Functionality that must be implemented by 2D vectors.
@@ -214,6 +227,306 @@ impl_log2!(f32, f64);
 #[cfg(feature = "ultraviolet")]
 impl_log2!(f32x4, f32x8);
 
+/// Returns the square root of self.
+pub trait Sqrt {
+    fn sqrt(&self) -> Self;
+}
+
+macro_rules! impl_sqrt {
+    ($($scalar_t:ty),+) => {
+        $(impl Sqrt for $scalar_t {
+            #[inline]
+            fn sqrt(&self) -> Self {
+                <$scalar_t>::sqrt(*self)
+            }
+        })+
+    }
+}
+
+impl_sqrt!(f32, f64);
+#[cfg(feature = "ultraviolet")]
+impl_sqrt!(f32x4, f32x8);
+
+/// Returns the sine and cosine of self, in radians.
+pub trait SinCos: Sized {
+    fn sin_cos(&self) -> (Self, Self);
+}
+
+macro_rules! impl_sin_cos {
+    ($($scalar_t:ty),+) => {
+        $(impl SinCos for $scalar_t {
+            #[inline]
+            fn sin_cos(&self) -> (Self, Self) {
+                <$scalar_t>::sin_cos(*self)
+            }
+        })+
+    }
+}
+
+impl_sin_cos!(f32, f64);
+#[cfg(feature = "ultraviolet")]
+impl_sin_cos!(f32x4, f32x8);
+
+/// Returns the four quadrant arctangent of self (`y`) and `x`, in radians.
+pub trait Atan2 {
+    fn atan2(&self, x: Self) -> Self;
+}
+
+macro_rules! impl_atan2 {
+    ($($scalar_t:ty),+) => {
+        $(impl Atan2 for $scalar_t {
+            #[inline]
+            fn atan2(&self, x: Self) -> Self {
+                <$scalar_t>::atan2(*self, x)
+            }
+        })+
+    }
+}
+
+impl_atan2!(f32, f64);
+#[cfg(feature = "ultraviolet")]
+impl_atan2!(f32x4, f32x8);
+
+/// Rounds self to the nearest integer. Values halfway between two integers may be
+/// rounded either way.
+pub trait RoundNearest {
+    fn round_nearest(&self) -> Self;
+}
+
+macro_rules! impl_round_nearest {
+    ($($scalar_t:ty),+) => {
+        $(impl RoundNearest for $scalar_t {
+            #[inline]
+            fn round_nearest(&self) -> Self {
+                <$scalar_t>::round(*self)
+            }
+        })+
+    }
+}
+
+impl_round_nearest!(f32, f64);
+#[cfg(feature = "ultraviolet")]
+impl_round_nearest!(f32x4, f32x8);
+
+/// Returns the cube root of self, which is negative for negative values.
+pub trait Cbrt {
+    fn cbrt(&self) -> Self;
+}
+
+macro_rules! impl_cbrt {
+    ($($scalar_t:ty),+) => {
+        $(impl Cbrt for $scalar_t {
+            #[inline]
+            fn cbrt(&self) -> Self {
+                <$scalar_t>::cbrt(*self)
+            }
+        })+
+    }
+}
+
+impl_cbrt!(f32, f64);
+
+#[cfg(feature = "ultraviolet")]
+macro_rules! impl_cbrt_wide {
+    ($($scalar_t:ty),+) => {
+        $(impl Cbrt for $scalar_t {
+            #[inline]
+            fn cbrt(&self) -> Self {
+                self.abs().powf(1.0 / 3.0).copysign(*self)
+            }
+        })+
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+impl_cbrt_wide!(f32x4, f32x8);
+
+/// Returns the arccosine of self, in radians.
+pub trait Acos {
+    fn acos(&self) -> Self;
+}
+
+macro_rules! impl_acos {
+    ($($scalar_t:ty),+) => {
+        $(impl Acos for $scalar_t {
+            #[inline]
+            fn acos(&self) -> Self {
+                <$scalar_t>::acos(*self)
+            }
+        })+
+    }
+}
+
+impl_acos!(f32, f64);
+#[cfg(feature = "ultraviolet")]
+impl_acos!(f32x4, f32x8);
+
+/// A scalar with a single lane, i.e. `f32` or `f64`.
+///
+/// Algorithms which need to branch on, sort, or index with individual values, such as
+/// mesh extraction, are only implemented for `Real` scalars rather than SIMD ones.
+pub trait Real:
+    Copy
+    + PartialOrd
+    + std::fmt::Debug
+    + Neg<Output = Self>
+    + Add<Self, Output = Self>
+    + Sub<Self, Output = Self>
+    + Mul<Self, Output = Self>
+    + Div<Self, Output = Self>
+    + Zero
+    + One
+    + PointFive
+    + MaxMin
+    + Clamp
+    + Sqrt
+{
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+}
+
+impl Real for f32 {
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Real for f64 {
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// A boolean mask, with one lane per lane of the scalar type it was produced from.
+///
+/// For regular scalars this is just `bool`, while for SIMD scalars like `f32x4` each lane
+/// holds its own result. This lets iterative algorithms like ray marching terminate each
+/// lane separately.
+pub trait Mask:
+    Copy + BitAnd<Self, Output = Self> + BitOr<Self, Output = Self> + Not<Output = Self>
+{
+    /// Create a mask with every lane set to `value`.
+    fn splat(value: bool) -> Self;
+    /// Returns `true` if any lane is set.
+    fn any(&self) -> bool;
+    /// Returns `true` if every lane is set.
+    fn all(&self) -> bool;
+}
+
+impl Mask for bool {
+    #[inline]
+    fn splat(value: bool) -> Self {
+        value
+    }
+    #[inline]
+    fn any(&self) -> bool {
+        *self
+    }
+    #[inline]
+    fn all(&self) -> bool {
+        *self
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+macro_rules! impl_mask_wide {
+    ($($scalar_t:ty),+) => {
+        $(impl Mask for $scalar_t {
+            #[inline]
+            fn splat(value: bool) -> Self {
+                let none = <$scalar_t>::from(0.0);
+                if value {
+                    !none
+                } else {
+                    none
+                }
+            }
+            #[inline]
+            fn any(&self) -> bool {
+                <$scalar_t>::any(*self)
+            }
+            #[inline]
+            fn all(&self) -> bool {
+                <$scalar_t>::all(*self)
+            }
+        })+
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+impl_mask_wide!(f32x4, f32x8);
+
+/// Compare scalars lane by lane and select between them based on the result.
+pub trait Compare: Sized {
+    type Mask: Mask;
+    /// Lanes where `self < other`.
+    fn cmp_lt(&self, other: Self) -> Self::Mask;
+    /// Lanes where `self > other`.
+    fn cmp_gt(&self, other: Self) -> Self::Mask;
+    /// Take the lanes of `if_true` where `mask` is set and the lanes of `if_false` elsewhere.
+    fn select(mask: Self::Mask, if_true: Self, if_false: Self) -> Self;
+}
+
+macro_rules! impl_compare {
+    ($($scalar_t:ty),+) => {
+        $(impl Compare for $scalar_t {
+            type Mask = bool;
+            #[inline]
+            fn cmp_lt(&self, other: Self) -> bool {
+                *self < other
+            }
+            #[inline]
+            fn cmp_gt(&self, other: Self) -> bool {
+                *self > other
+            }
+            #[inline]
+            fn select(mask: bool, if_true: Self, if_false: Self) -> Self {
+                if mask {
+                    if_true
+                } else {
+                    if_false
+                }
+            }
+        })+
+    }
+}
+
+impl_compare!(f32, f64);
+
+#[cfg(feature = "ultraviolet")]
+macro_rules! impl_compare_wide {
+    ($($scalar_t:ty),+) => {
+        $(impl Compare for $scalar_t {
+            type Mask = $scalar_t;
+            #[inline]
+            fn cmp_lt(&self, other: Self) -> Self {
+                (*self - other).sign_bit()
+            }
+            #[inline]
+            fn cmp_gt(&self, other: Self) -> Self {
+                (other - *self).sign_bit()
+            }
+            #[inline]
+            fn select(mask: Self, if_true: Self, if_false: Self) -> Self {
+                mask.blend(if_true, if_false)
+            }
+        })+
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+impl_compare_wide!(f32x4, f32x8);
+
 /// This is a trait for types that can rotate an SDF.
 /// Note that the implementation should actually rotate the vec
 /// by the *inverse* of the native rotation that the type would
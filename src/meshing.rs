@@ -0,0 +1,593 @@
+//! Extracting meshes from 3D SDFs and contours from 2D SDFs by sampling them over a grid.
+//!
+//! The SDF is sampled at the corners of every cell of a regular grid spanning an
+//! axis-aligned bounding box, and the surface is reconstructed wherever the sign of the
+//! sampled distance changes. Surfaces that are cut by the bounding box are left open
+//! at the boundary.
+//!
+//! `marching_cubes` places vertices on the edges of the grid, while `surface_nets` and
+//! `dual_contouring` place one vertex inside each cell the surface passes through and
+//! connect them with quads. Dual contouring positions those vertices using the normals of the
+//! surface, which lets it reproduce sharp edges and corners that the other methods round off.
+use crate::mathtypes::{Real, Vec2, Vec3};
+use crate::util::*;
+use crate::SDF;
+use std::collections::{HashMap, HashSet};
+
+/// An indexed polygon mesh made up of triangles and quads.
+#[derive(Clone, Debug)]
+pub struct Mesh<V> {
+    pub vertices: Vec<V>,
+    /// Per-vertex normals, if they have been computed with `estimate_normals`.
+    pub normals: Option<Vec<V>>,
+    /// Triangles as indices into `vertices`, wound counter-clockwise when viewed from
+    /// outside the surface.
+    pub triangles: Vec<[u32; 3]>,
+    /// Quads as indices into `vertices`, wound counter-clockwise when viewed from
+    /// outside the surface.
+    pub quads: Vec<[u32; 4]>,
+}
+
+impl<V> Mesh<V> {
+    /// Creates an empty mesh.
+    pub fn new() -> Self {
+        Mesh {
+            vertices: Vec::new(),
+            normals: None,
+            triangles: Vec::new(),
+            quads: Vec::new(),
+        }
+    }
+
+    /// Splits every quad of this mesh into two triangles, leaving a mesh made up only
+    /// of triangles.
+    pub fn triangulate(&mut self) {
+        for [a, b, c, d] in self.quads.drain(..) {
+            self.triangles.push([a, b, c]);
+            self.triangles.push([a, c, d]);
+        }
+    }
+
+    /// Estimates the normal at each vertex of this mesh from `sdf` using `estimator`.
+    pub fn estimate_normals<T, S, E>(&mut self, sdf: S, estimator: &E)
+    where
+        V: Vec3<T>,
+        S: SDF<T, V>,
+        E: NormalEstimator<T, V>,
+    {
+        self.normals = Some(
+            self.vertices
+                .iter()
+                .map(|&v| estimator.estimate_normal(sdf, v))
+                .collect(),
+        );
+    }
+}
+
+impl<V> Default for Mesh<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A polyline traced along a contour of a 2D SDF.
+#[derive(Clone, Debug)]
+pub struct Polyline<V> {
+    pub points: Vec<V>,
+    /// Whether the last point connects back to the first one. Contours which are cut by the
+    /// bounds they were extracted from are left open.
+    pub closed: bool,
+}
+
+/// The values of an SDF sampled at the points of a regular 3D grid.
+pub(crate) struct Samples<T, V> {
+    pub min: V,
+    pub size: V,
+    pub resolution: [usize; 3],
+    pub values: Vec<T>,
+}
+
+impl<T: Real, V: Vec3<T>> Samples<T, V> {
+    /// Samples `sdf` at the corners of a grid with `resolution` cells along each axis,
+    /// spanning from `min` to `max`.
+    pub fn new<S: SDF<T, V>>(sdf: S, min: V, max: V, resolution: [usize; 3]) -> Self {
+        let mut samples = Samples {
+            min,
+            size: max - min,
+            resolution,
+            values: Vec::with_capacity(
+                (resolution[0] + 1) * (resolution[1] + 1) * (resolution[2] + 1),
+            ),
+        };
+        for z in 0..=resolution[2] {
+            for y in 0..=resolution[1] {
+                for x in 0..=resolution[0] {
+                    let p = samples.point(x, y, z);
+                    samples.values.push(sdf.dist(p));
+                }
+            }
+        }
+        samples
+    }
+
+    /// The position of the grid point with the given coordinates.
+    #[inline]
+    pub fn point(&self, x: usize, y: usize, z: usize) -> V {
+        let fraction = |i: usize, n: usize| T::from_f64(i as f64 / n as f64);
+        V::new(
+            self.min.x() + self.size.x() * fraction(x, self.resolution[0]),
+            self.min.y() + self.size.y() * fraction(y, self.resolution[1]),
+            self.min.z() + self.size.z() * fraction(z, self.resolution[2]),
+        )
+    }
+
+    /// The index into `values` of the grid point with the given coordinates.
+    #[inline]
+    pub fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        x + (self.resolution[0] + 1) * (y + (self.resolution[1] + 1) * z)
+    }
+
+    /// The point between the grid points `a` and `b` where the sampled values cross zero.
+    #[inline]
+    pub fn crossing(&self, [ax, ay, az]: [usize; 3], [bx, by, bz]: [usize; 3]) -> V {
+        let va = self.values[self.index(ax, ay, az)];
+        let vb = self.values[self.index(bx, by, bz)];
+        let pa = self.point(ax, ay, az);
+        let pb = self.point(bx, by, bz);
+        pa + (pb - pa) * (va / (va - vb))
+    }
+
+    /// The grid coordinates of the corner of the cell at `(x, y, z)` with index `corner`,
+    /// where bits 0, 1 and 2 of `corner` offset the cell along X, Y and Z respectively.
+    #[inline]
+    pub fn corner(x: usize, y: usize, z: usize, corner: usize) -> [usize; 3] {
+        [
+            x + (corner & 1),
+            y + ((corner >> 1) & 1),
+            z + ((corner >> 2) & 1),
+        ]
+    }
+}
+
+/// The corners of each face of a grid cell, counter-clockwise when viewed from outside the
+/// cell. Corners are indexed as in `Samples::corner`.
+const CELL_FACES: [[usize; 4]; 6] = [
+    [0, 4, 6, 2],
+    [1, 3, 7, 5],
+    [0, 1, 5, 4],
+    [2, 6, 7, 3],
+    [0, 2, 3, 1],
+    [4, 5, 7, 6],
+];
+
+/// Extracts the surface of `sdf` as a triangle mesh using marching cubes.
+///
+/// The SDF is sampled over the box from `min` to `max`, which is split into `resolution`
+/// cells along each axis. Triangles are wound counter-clockwise when viewed from outside of
+/// the surface. Rather than a fixed lookup table, the polygon in each cell is found by
+/// tracing the contour across the cell's faces, and ambiguous faces are resolved using the
+/// value at their center. Since neighboring cells make the same choice for the face they
+/// share and share the vertices on their common edges, the resulting mesh is watertight
+/// for closed surfaces that lie inside the bounds.
+///
+/// Normals are not computed; see `Mesh::estimate_normals`.
+pub fn marching_cubes<T, V, S>(sdf: S, min: V, max: V, resolution: [usize; 3]) -> Mesh<V>
+where
+    T: Real,
+    V: Vec3<T>,
+    S: SDF<T, V>,
+{
+    let samples = Samples::new(sdf, min, max, resolution);
+    let mut mesh = Mesh::new();
+    let mut edge_vertices = HashMap::new();
+
+    for z in 0..resolution[2] {
+        for y in 0..resolution[1] {
+            for x in 0..resolution[0] {
+                let mut values = [T::zero(); 8];
+                for (corner, value) in values.iter_mut().enumerate() {
+                    let [cx, cy, cz] = Samples::<T, V>::corner(x, y, z, corner);
+                    *value = samples.values[samples.index(cx, cy, cz)];
+                }
+
+                let inside = |corner: usize| values[corner] < T::zero();
+                if (0..8).all(inside) || !(0..8).any(inside) {
+                    continue;
+                }
+
+                // Maps each cell edge where the contour enters a face to the cell edge where it
+                // leaves it, with edges identified by their lower corner and axis bit.
+                let mut next = [None; 24];
+                for face in CELL_FACES.iter() {
+                    trace_face(face, &values, &mut next);
+                }
+
+                let mut visited = [false; 24];
+                for start in 0..24 {
+                    if next[start].is_none() || visited[start] {
+                        continue;
+                    }
+                    let mut polygon = Vec::new();
+                    let mut edge = start;
+                    while !visited[edge] {
+                        visited[edge] = true;
+                        polygon.push(edge_vertex(
+                            &samples,
+                            &mut mesh,
+                            &mut edge_vertices,
+                            [x, y, z],
+                            edge,
+                        ));
+                        edge = next[edge].unwrap();
+                    }
+                    for i in 1..polygon.len().saturating_sub(1) {
+                        mesh.triangles
+                            .push([polygon[0], polygon[i + 1], polygon[i]]);
+                    }
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// Finds the contour segments crossing a square cell or cell face, given the values at its
+/// corners in counter-clockwise order.
+///
+/// Each segment is returned as a pair of edges, where edge `i` connects corners `i` and
+/// `i + 1`. Segments are oriented so that the inside of the surface is on their left.
+fn contour_segments<T: Real>(values: [T; 4]) -> [Option<(usize, usize)>; 2] {
+    let inside = |i: usize| values[i % 4] < T::zero();
+    let mut exits = (0..4).filter(|&i| inside(i) && !inside(i + 1));
+    let mut segments = [None; 2];
+
+    match (exits.next(), exits.next()) {
+        (Some(exit), None) => {
+            let entry = (0..4).find(|&i| !inside(i) && inside(i + 1)).unwrap();
+            segments[0] = Some((exit, entry));
+        }
+        (Some(first), Some(second)) => {
+            // The inside corners are diagonal to each other, so decide whether they are
+            // connected across the cell using the value at its center.
+            let center = values.iter().fold(T::zero(), |sum, &v| sum + v);
+            let offset = if center < T::zero() { 1 } else { 3 };
+            segments[0] = Some((first, (first + offset) % 4));
+            segments[1] = Some((second, (second + offset) % 4));
+        }
+        _ => {}
+    }
+
+    segments
+}
+
+/// Finds the contour segments crossing a cell face and records them in `next`, oriented so
+/// that the inside of the surface is on their left when viewed from outside the cell.
+fn trace_face<T: Real>(face: &[usize; 4], values: &[T; 8], next: &mut [Option<usize>; 24]) {
+    let edge = |i: usize| {
+        let (a, b) = (face[i], face[(i + 1) % 4]);
+        (a & b) * 3 + (a ^ b).trailing_zeros() as usize
+    };
+    let face_values = [
+        values[face[0]],
+        values[face[1]],
+        values[face[2]],
+        values[face[3]],
+    ];
+    for &(exit, entry) in contour_segments(face_values).iter().flatten() {
+        next[edge(exit)] = Some(edge(entry));
+    }
+}
+
+/// Gets the index of the mesh vertex on a cell edge, creating it if it doesn't exist yet.
+fn edge_vertex<T: Real, V: Vec3<T>>(
+    samples: &Samples<T, V>,
+    mesh: &mut Mesh<V>,
+    edge_vertices: &mut HashMap<(usize, usize), u32>,
+    [x, y, z]: [usize; 3],
+    edge: usize,
+) -> u32 {
+    let (corner, axis) = (edge / 3, edge % 3);
+    let a = Samples::<T, V>::corner(x, y, z, corner);
+    let b = Samples::<T, V>::corner(x, y, z, corner | (1 << axis));
+    *edge_vertices
+        .entry((samples.index(a[0], a[1], a[2]), axis))
+        .or_insert_with(|| {
+            mesh.vertices.push(samples.crossing(a, b));
+            (mesh.vertices.len() - 1) as u32
+        })
+}
+
+/// Extracts the surface of `sdf` as a quad mesh using naive surface nets.
+///
+/// The SDF is sampled over the box from `min` to `max`, which is split into `resolution`
+/// cells along each axis. A vertex is placed in every cell the surface passes through, at the
+/// average of the points where the surface crosses the cell's edges, and the vertices of the
+/// four cells around every crossed edge are connected by a quad. Use `Mesh::triangulate` to get
+/// a triangle mesh instead.
+///
+/// Normals are not computed; see `Mesh::estimate_normals`.
+pub fn surface_nets<T, V, S>(sdf: S, min: V, max: V, resolution: [usize; 3]) -> Mesh<V>
+where
+    T: Real,
+    V: Vec3<T>,
+    S: SDF<T, V>,
+{
+    dual_mesh(sdf, min, max, resolution, |crossings, _| {
+        mass_point(crossings)
+    })
+}
+
+/// Extracts the surface of `sdf` as a quad mesh using dual contouring.
+///
+/// This works like `surface_nets`, but instead of averaging the points where the surface
+/// crosses each cell's edges, it estimates the surface normal at each of them with `estimator`
+/// and places the cell's vertex at the point that best fits all of the tangent planes, by
+/// minimizing a quadratic error function. That allows sharp edges and corners, such as those of
+/// a `Box` or of a union using `HardMin`, to be reproduced.
+///
+/// Normals are not computed; see `Mesh::estimate_normals`.
+pub fn dual_contouring<T, V, S, E>(
+    sdf: S,
+    min: V,
+    max: V,
+    resolution: [usize; 3],
+    estimator: &E,
+) -> Mesh<V>
+where
+    T: Real,
+    V: Vec3<T>,
+    S: SDF<T, V>,
+    E: NormalEstimator<T, V>,
+{
+    dual_mesh(sdf, min, max, resolution, |crossings, [lo, hi]| {
+        let normals: Vec<V> = crossings
+            .iter()
+            .map(|&p| estimator.estimate_normal(sdf, p))
+            .collect();
+        minimize_qef(crossings, &normals).clamp(lo, hi)
+    })
+}
+
+/// Places a vertex in each cell of the grid that the surface passes through using `place`,
+/// and connects them with quads. `place` is given the points where the surface crosses the
+/// edges of the cell and the cell's bounds.
+fn dual_mesh<T, V, S, F>(sdf: S, min: V, max: V, resolution: [usize; 3], place: F) -> Mesh<V>
+where
+    T: Real,
+    V: Vec3<T>,
+    S: SDF<T, V>,
+    F: Fn(&[V], [V; 2]) -> V,
+{
+    let samples = Samples::new(sdf, min, max, resolution);
+    let mut mesh = Mesh::new();
+    let [rx, ry, rz] = resolution;
+    let cell_index = |x: usize, y: usize, z: usize| x + rx * (y + ry * z);
+    let mut cell_vertices = vec![u32::MAX; rx * ry * rz];
+
+    for z in 0..rz {
+        for y in 0..ry {
+            for x in 0..rx {
+                let mut crossings = Vec::new();
+                for corner in 0..8 {
+                    for axis in 0..3 {
+                        if corner & (1 << axis) != 0 {
+                            continue;
+                        }
+                        let a = Samples::<T, V>::corner(x, y, z, corner);
+                        let b = Samples::<T, V>::corner(x, y, z, corner | (1 << axis));
+                        let va = samples.values[samples.index(a[0], a[1], a[2])];
+                        let vb = samples.values[samples.index(b[0], b[1], b[2])];
+                        if (va < T::zero()) != (vb < T::zero()) {
+                            crossings.push(samples.crossing(a, b));
+                        }
+                    }
+                }
+                if crossings.is_empty() {
+                    continue;
+                }
+                let bounds = [samples.point(x, y, z), samples.point(x + 1, y + 1, z + 1)];
+                mesh.vertices.push(place(&crossings, bounds));
+                cell_vertices[cell_index(x, y, z)] = (mesh.vertices.len() - 1) as u32;
+            }
+        }
+    }
+
+    // Every crossed grid edge that is surrounded by four cells gets a quad connecting them.
+    for z in 0..=rz {
+        for y in 0..=ry {
+            for x in 0..=rx {
+                let p = [x, y, z];
+                let inside = samples.values[samples.index(x, y, z)] < T::zero();
+                for axis in 0..3 {
+                    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+                    if p[axis] == resolution[axis]
+                        || p[u] == 0
+                        || p[v] == 0
+                        || p[u] == resolution[u]
+                        || p[v] == resolution[v]
+                    {
+                        continue;
+                    }
+                    let mut q = p;
+                    q[axis] += 1;
+                    if inside == (samples.values[samples.index(q[0], q[1], q[2])] < T::zero()) {
+                        continue;
+                    }
+
+                    // The cells around the edge, counter-clockwise when viewed from the
+                    // positive end of the edge's axis.
+                    let mut quad = [0; 4];
+                    for (vertex, (du, dv)) in quad.iter_mut().zip([(1, 1), (0, 1), (0, 0), (1, 0)])
+                    {
+                        let mut c = p;
+                        c[u] -= du;
+                        c[v] -= dv;
+                        *vertex = cell_vertices[cell_index(c[0], c[1], c[2])];
+                    }
+                    if !inside {
+                        quad.reverse();
+                    }
+                    mesh.quads.push(quad);
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// The average of `points`.
+fn mass_point<T: Real, V: Vec3<T>>(points: &[V]) -> V {
+    let sum = points.iter().fold(V::zero(), |sum, &p| sum + p);
+    sum / T::from_f64(points.len() as f64)
+}
+
+/// Finds the point which minimizes the sum of squared distances to the planes through
+/// `points` with `normals`.
+///
+/// The solution is biased towards the mass point of `points`, which keeps it well defined
+/// when the planes are (nearly) parallel, such as on flat or smoothly curved parts of the
+/// surface.
+fn minimize_qef<T: Real, V: Vec3<T>>(points: &[V], normals: &[V]) -> V {
+    let center = mass_point::<T, V>(points);
+    let bias = T::from_f64(0.05);
+
+    // Solve (AᵀA + bias * I) x = Aᵀb, where the rows of A are the normals and b holds the
+    // distances from the mass point to each plane.
+    let mut ata = [[T::zero(); 3]; 3];
+    let mut atb = [T::zero(); 3];
+    for (&p, &n) in points.iter().zip(normals.iter()) {
+        let n = [n.x(), n.y(), n.z()];
+        let d =
+            n[0] * (p.x() - center.x()) + n[1] * (p.y() - center.y()) + n[2] * (p.z() - center.z());
+        for i in 0..3 {
+            for j in 0..3 {
+                ata[i][j] = ata[i][j] + n[i] * n[j];
+            }
+            atb[i] = atb[i] + n[i] * d;
+        }
+    }
+    for (i, row) in ata.iter_mut().enumerate() {
+        row[i] = row[i] + bias;
+    }
+
+    let det = |m: &[[T; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let denominator = det(&ata);
+    let mut x = [T::zero(); 3];
+    for (i, x) in x.iter_mut().enumerate() {
+        let mut m = ata;
+        for (row, &b) in m.iter_mut().zip(atb.iter()) {
+            row[i] = b;
+        }
+        *x = det(&m) / denominator;
+    }
+
+    center + V::new(x[0], x[1], x[2])
+}
+
+/// Extracts the contour of `sdf` where its value equals `iso` as polylines using marching
+/// squares.
+///
+/// The SDF is sampled over the rectangle from `min` to `max`, which is split into
+/// `resolution` cells along each axis. An `iso` of zero gives the outline of the SDF, while
+/// other values give outlines offset outwards (for positive values) or inwards (for negative
+/// ones).
+///
+/// Polylines are oriented so that the inside of the contour is on their left, which means that
+/// outer boundaries run counter-clockwise and the boundaries of holes run clockwise.
+/// Ambiguous cells are resolved using the value at their center.
+pub fn marching_squares<T, V, S>(
+    sdf: S,
+    min: V,
+    max: V,
+    resolution: [usize; 2],
+    iso: T,
+) -> Vec<Polyline<V>>
+where
+    T: Real,
+    V: Vec2<T>,
+    S: SDF<T, V>,
+{
+    let [rx, ry] = resolution;
+    let size = max - min;
+    let point = |x: usize, y: usize| {
+        V::new(
+            min.x() + size.x() * T::from_f64(x as f64 / rx as f64),
+            min.y() + size.y() * T::from_f64(y as f64 / ry as f64),
+        )
+    };
+    let index = |x: usize, y: usize| x + (rx + 1) * y;
+    let mut values = Vec::with_capacity((rx + 1) * (ry + 1));
+    for y in 0..=ry {
+        for x in 0..=rx {
+            values.push(sdf.dist(point(x, y)) - iso);
+        }
+    }
+
+    // Grid edges are identified by their lower grid point and their axis. Each segment maps
+    // the edge where the contour enters a cell to the edge where it leaves it.
+    let mut next = HashMap::new();
+    for y in 0..ry {
+        for x in 0..rx {
+            let edges = [(x, y, 0), (x + 1, y, 1), (x, y + 1, 0), (x, y, 1)];
+            let cell_values = [
+                values[index(x, y)],
+                values[index(x + 1, y)],
+                values[index(x + 1, y + 1)],
+                values[index(x, y + 1)],
+            ];
+            for &(exit, entry) in contour_segments(cell_values).iter().flatten() {
+                next.insert(edges[exit], edges[entry]);
+            }
+        }
+    }
+
+    let crossing = |(x, y, axis): (usize, usize, usize)| {
+        let (bx, by) = if axis == 0 { (x + 1, y) } else { (x, y + 1) };
+        let (va, vb) = (values[index(x, y)], values[index(bx, by)]);
+        let (pa, pb) = (point(x, y), point(bx, by));
+        pa + (pb - pa) * (va / (va - vb))
+    };
+
+    // Open polylines start at edges that no segment leads into, i.e. on the bounds.
+    let ends: HashSet<_> = next.values().copied().collect();
+    let mut starts: Vec<_> = next.keys().filter(|e| !ends.contains(e)).copied().collect();
+    starts.sort_unstable();
+    let mut polylines = Vec::new();
+    for start in starts {
+        let mut points = vec![crossing(start)];
+        let mut edge = start;
+        while let Some(following) = next.remove(&edge) {
+            points.push(crossing(following));
+            edge = following;
+        }
+        polylines.push(Polyline {
+            points,
+            closed: false,
+        });
+    }
+
+    let mut remaining: Vec<_> = next.keys().copied().collect();
+    remaining.sort_unstable();
+    for start in remaining {
+        let mut points = Vec::new();
+        let mut edge = start;
+        while let Some(following) = next.remove(&edge) {
+            points.push(crossing(edge));
+            edge = following;
+        }
+        if !points.is_empty() {
+            polylines.push(Polyline {
+                points,
+                closed: true,
+            });
+        }
+    }
+
+    polylines
+}
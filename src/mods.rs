@@ -132,6 +132,147 @@ where
     }
 }
 
+/// Extrude a 2D SDF into a 3D solid along an axis, extending `half_height` to either side
+/// of the origin. The X and Y axes of the 2D SDF map to the two other axes in order, e.g. to
+/// X and Z when extruding along Y.
+#[derive(Clone, Copy, Debug)]
+pub struct Extrude<T, S> {
+    pub sdf: S,
+    pub axis: Axis,
+    pub half_height: T,
+}
+
+impl<T, S> Extrude<T, S> {
+    pub fn new(sdf: S, axis: Axis, half_height: T) -> Self {
+        Extrude {
+            sdf,
+            axis,
+            half_height,
+        }
+    }
+}
+
+impl<T, V, S> SDF<T, V> for Extrude<T, S>
+where
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Neg<Output = T> + Zero + MaxMin,
+    V: Vec3<T>,
+    S: SDF<T, V::Vec2>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (a, b, c) = self.axis.split(p);
+        let d = self.sdf.dist(V::Vec2::new(a, b));
+        crate::primitives::extrude::<T, V::Vec2>(d, c, self.half_height)
+    }
+}
+
+/// Revolve a 2D SDF around an axis into a 3D solid. The X axis of the 2D SDF points away
+/// from the axis of revolution, offset from it by `offset`, and its Y axis runs along it.
+#[derive(Clone, Copy, Debug)]
+pub struct Revolve<T, S> {
+    pub sdf: S,
+    pub axis: Axis,
+    pub offset: T,
+}
+
+impl<T, S> Revolve<T, S> {
+    pub fn new(sdf: S, axis: Axis, offset: T) -> Self {
+        Revolve { sdf, axis, offset }
+    }
+}
+
+impl<T, V, S> SDF<T, V> for Revolve<T, S>
+where
+    T: Copy + Sub<T, Output = T>,
+    V: Vec3<T>,
+    S: SDF<T, V::Vec2>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (r, c) = self.axis.radial(p);
+        self.sdf.dist(V::Vec2::new(r - self.offset, c))
+    }
+}
+
+/// Revolve a 2D SDF around an axis like `Revolve`, but only between the angles `start` and
+/// `end`, in radians. Angles are measured around the axis from the first of the two other
+/// axes towards the second, e.g. from X towards Z when revolving around Y. `end` must be
+/// greater than `start`, by no more than a full turn.
+///
+/// The 2D SDF must be entirely on the positive side of its Y axis once offset. The distance
+/// is then exact outside the solid, and a bound inside it.
+#[derive(Clone, Copy, Debug)]
+pub struct RevolvePartial<T, S> {
+    pub sdf: S,
+    pub axis: Axis,
+    pub offset: T,
+    pub start: T,
+    pub end: T,
+}
+
+impl<T, S> RevolvePartial<T, S> {
+    pub fn new(sdf: S, axis: Axis, offset: T, start: T, end: T) -> Self {
+        RevolvePartial {
+            sdf,
+            axis,
+            offset,
+            start,
+            end,
+        }
+    }
+}
+
+impl<T, V, S> SDF<T, V> for RevolvePartial<T, S>
+where
+    T: Copy
+        + Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + PointFive
+        + MaxMin
+        + Sqrt
+        + SinCos
+        + Atan2
+        + Compare,
+    V: Vec3<T>,
+    S: SDF<T, V::Vec2>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (a, b, c) = self.axis.split(p);
+        let full = self.sdf.dist(V::Vec2::new(
+            V::Vec2::new(a, b).magnitude() - self.offset,
+            c,
+        ));
+        // The distances to the plane of each end and to the profile lying in it.
+        let end = |angle: T| {
+            let (sin, cos) = angle.sin_cos();
+            let h = b * cos - a * sin;
+            let d = self
+                .sdf
+                .dist(V::Vec2::new(a * cos + b * sin - self.offset, c))
+                .max(T::zero());
+            (h.max(-h), (d * d + h * h).sqrt())
+        };
+        let (start_plane, start_cap) = end(self.start);
+        let (end_plane, end_cap) = end(self.end);
+
+        // Within the angles, the closest point of the solid is the same as for a full
+        // revolution, and elsewhere it is on one of the ends.
+        let (sin, cos) = ((self.start + self.end) * T::point_five()).sin_cos();
+        let angle = (b * cos - a * sin).atan2(a * cos + b * sin);
+        let half = (self.end - self.start) * T::point_five();
+        let inside = angle.max(-angle).cmp_lt(half);
+        T::select(
+            inside,
+            full.max(-start_plane).max(-end_plane),
+            start_cap.min(end_cap),
+        )
+    }
+}
+
 /// Translate an SDF.
 #[derive(Clone, Copy, Debug)]
 pub struct Translate<V, S> {
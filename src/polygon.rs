@@ -0,0 +1,132 @@
+//! An SDF for arbitrary 2D polygons, such as outlines imported from vector graphics.
+//!
+//! The distance is the exact distance to the closest edge, and the sign is determined by
+//! counting how the edges wind around the point according to a `FillRule`, so polygons may
+//! self-intersect and have holes.
+use crate::mathtypes::{Clamp, Compare, MaxMin, One, PointFive, Sqrt, Vec2, Zero};
+use crate::primitives::segment_dist2;
+use crate::SDF;
+use std::ops::*;
+
+/// How the winding of a polygon's edges around a point determines whether it is inside.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses an odd number of edges. Holes can be wound
+    /// in either direction.
+    EvenOdd,
+    /// A point is inside if the edges wind around it a nonzero number of times. Holes must
+    /// be wound in the opposite direction to the rings around them.
+    NonZero,
+}
+
+/// A 2D polygon made up of one or more closed rings of vertices. Each ring is implicitly
+/// closed, so the last vertex should not repeat the first.
+///
+/// Since the polygon owns its data, it does not implement `SDF` itself; instead, a reference
+/// to it does. This means it can be combined with other SDFs like so:
+///
+/// ```rust
+/// # #[cfg(feature = "ultraviolet")]
+/// # fn main() {
+/// use sdfu::SDF;
+/// use ultraviolet::Vec2;
+///
+/// let arrow = sdfu::polygon::Polygon2D::new(vec![
+///     Vec2::new(-1.0, -0.25),
+///     Vec2::new(0.5, -0.25),
+///     Vec2::new(0.5, -0.75),
+///     Vec2::new(1.5, 0.0),
+///     Vec2::new(0.5, 0.75),
+///     Vec2::new(0.5, 0.25),
+///     Vec2::new(-1.0, 0.25),
+/// ]);
+/// let sdf = (&arrow).round(0.1);
+/// assert!(sdf.dist(Vec2::new(1.0, 0.0)) < 0.0);
+/// # }
+/// # #[cfg(not(feature = "ultraviolet"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug)]
+pub struct Polygon2D<V> {
+    rings: Vec<Vec<V>>,
+    pub fill_rule: FillRule,
+}
+
+impl<V> Polygon2D<V> {
+    /// Creates a polygon from a single ring of vertices, filled with `FillRule::EvenOdd`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vertices` is empty.
+    pub fn new(vertices: Vec<V>) -> Self {
+        Self::from_rings(vec![vertices], FillRule::EvenOdd)
+    }
+
+    /// Creates a polygon from several rings, such as an outline and the holes within it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if none of the rings have any vertices.
+    pub fn from_rings(rings: Vec<Vec<V>>, fill_rule: FillRule) -> Self {
+        assert!(
+            rings.iter().any(|ring| !ring.is_empty()),
+            "Polygon2D must have at least one vertex"
+        );
+        Polygon2D { rings, fill_rule }
+    }
+
+    /// The rings of vertices the polygon was created from.
+    pub fn rings(&self) -> &[Vec<V>] {
+        &self.rings
+    }
+}
+
+impl<T, V> SDF<T, V> for &Polygon2D<V>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec2<T>,
+{
+    fn dist(&self, p: V) -> T {
+        let mut dist2 = None;
+        let mut winding = T::zero();
+        for ring in self.rings.iter().filter(|ring| !ring.is_empty()) {
+            let mut a = ring[ring.len() - 1];
+            for &b in ring {
+                let (e, w) = (b - a, p - a);
+                let d = segment_dist2(w, e);
+                dist2 = Some(dist2.map_or(d, |m: T| m.min(d)));
+                // A ray from `p` along +x crosses the edge upwards if the edge passes `p` on the
+                // left, and downwards if it passes on the right.
+                let side = e.x() * w.y() - e.y() * w.x();
+                let up = !p.y().cmp_lt(a.y()) & p.y().cmp_lt(b.y()) & side.cmp_gt(T::zero());
+                let down = !p.y().cmp_lt(b.y()) & p.y().cmp_lt(a.y()) & side.cmp_lt(T::zero());
+                winding = match self.fill_rule {
+                    FillRule::EvenOdd => T::select(up | down, T::one() - winding, winding),
+                    FillRule::NonZero => T::select(
+                        up,
+                        winding + T::one(),
+                        T::select(down, winding - T::one(), winding),
+                    ),
+                };
+                a = b;
+            }
+        }
+        // The constructors make sure there is at least one vertex, and so one edge.
+        let dist = dist2.unwrap().sqrt();
+        // Winding numbers are whole, so anything away from zero is inside.
+        let inside = (winding * winding).cmp_gt(T::point_five());
+        T::select(inside, -dist, dist)
+    }
+}
@@ -134,6 +134,35 @@ pub enum Axis {
     Z,
 }
 
+impl Axis {
+    /// Splits `p` into its two coordinates perpendicular to this axis and its coordinate
+    /// along it, in the same order as `CappedCylinder` uses.
+    #[inline]
+    pub(crate) fn split<T, V: Vec3<T>>(self, p: V) -> (T, T, T) {
+        match self {
+            Axis::X => (p.y(), p.z(), p.x()),
+            Axis::Y => (p.x(), p.z(), p.y()),
+            Axis::Z => (p.x(), p.y(), p.z()),
+        }
+    }
+
+    /// Splits `p` into its distance from this axis and its coordinate along it.
+    #[inline]
+    pub(crate) fn radial<T, V: Vec3<T>>(self, p: V) -> (T, T) {
+        let (a, b, c) = self.split(p);
+        (V::Vec2::new(a, b).magnitude(), c)
+    }
+}
+
+/// Returns `value` negated where `negative` is set.
+#[inline]
+fn with_sign<T>(value: T, negative: T::Mask) -> T
+where
+    T: Compare + Neg<Output = T> + Copy,
+{
+    T::select(negative, -value, value)
+}
+
 /// An infinite cylinder extending along an axis.
 #[derive(Clone, Copy, Debug)]
 pub struct Cylinder<T> {
@@ -228,3 +257,2315 @@ where
         (pa - (ba * h)).magnitude() - self.thickness
     }
 }
+
+/// A tube of thickness `thickness` around a quadratic Bézier curve from `a` to `c`, with
+/// control point `b`.
+///
+/// The closest point on the curve is found exactly by solving a cubic, as described by
+/// [Inigo Quilez](https://iquilezles.org/articles/distfunctions2d/).
+#[derive(Clone, Copy, Debug)]
+pub struct QuadraticBezier<T, V> {
+    pub a: V,
+    pub b: V,
+    pub c: V,
+    pub thickness: T,
+}
+
+impl<T, V> QuadraticBezier<T, V> {
+    pub fn new(a: V, b: V, c: V, thickness: T) -> Self {
+        QuadraticBezier { a, b, c, thickness }
+    }
+}
+
+impl<T, V> SDF<T, V> for QuadraticBezier<T, V>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Cbrt
+        + Acos
+        + SinCos
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (zero, one) = (T::zero(), T::one());
+        // The curve is `a + (c + b * t) * t` relative to `p`.
+        let a = self.b - self.a;
+        let b = self.a - self.b - self.b + self.c;
+        let c = a + a;
+        let d = self.a - p;
+        // The closest point is a root of the cubic `t^3 + 3 kx t^2 + 3 ky t + kz`.
+        let kk = one / b.dot(b);
+        let kx = kk * a.dot(b);
+        let ky = kk * (a.dot(a) + a.dot(a) + d.dot(b)) / T::from(3.0);
+        let kz = kk * d.dot(a);
+        let p3 = ky - kx * kx;
+        let q = kx * (kx * kx + kx * kx - ky * T::from(3.0)) + kz;
+        let h = q * q + p3 * p3 * p3 * T::from(4.0);
+        let dist2 = |t: T| {
+            let v = d + (c + b * t) * t;
+            v.dot(v)
+        };
+
+        // With one real root, use Cardano's formula.
+        let sqrt_h = h.max(zero).sqrt();
+        let one_root = (((sqrt_h - q) * T::point_five()).cbrt()
+            + ((-sqrt_h - q) * T::point_five()).cbrt()
+            - kx)
+            .clamp(zero, one);
+        // With three, use the trigonometric solution. The middle root is a maximum, so only
+        // the other two need to be checked.
+        let z = (-p3).max(zero).sqrt();
+        let v = (q / (p3 * z * T::from(2.0))).clamp(-one, one).acos() / T::from(3.0);
+        let (sin, cos) = v.sin_cos();
+        let n = sin * T::from(3f32.sqrt());
+        let first = ((cos + cos) * z - kx).clamp(zero, one);
+        let second = ((-n - cos) * z - kx).clamp(zero, one);
+        let curve = T::select(
+            h.cmp_lt(zero),
+            dist2(first).min(dist2(second)),
+            dist2(one_root),
+        );
+
+        // When the control point lies halfway between the ends, the curve is a straight line
+        // and the cubic degenerates.
+        let straight = b.dot(b).cmp_lt(a.dot(a) * T::from(1e-8));
+        let line = segment_dist2(p - self.a, self.c - self.a);
+        T::select(straight, line, curve).sqrt() - self.thickness
+    }
+}
+
+/// The number of evenly spaced intervals on a `CubicBezier` whose ends are used as starting
+/// points when searching for the closest point.
+const CUBIC_BEZIER_SAMPLES: usize = 8;
+
+/// The number of Newton iterations used to refine each starting point on a `CubicBezier`.
+const CUBIC_BEZIER_STEPS: usize = 4;
+
+/// How many times a `CubicBezier` can be halved while bounding the distance to its parts.
+const CUBIC_BEZIER_DEPTH: usize = 16;
+
+/// How far below the exact distance the distance to a `CubicBezier` can be, relative to the
+/// length of its control polygon.
+const CUBIC_BEZIER_TOLERANCE: f32 = 1e-5;
+
+/// A tube of thickness `thickness` around a cubic Bézier curve from `a` to `d`, with
+/// control points `b` and `c`.
+///
+/// There is no closed form for the closest point on the curve, so it is found by refining
+/// evenly spaced samples along the curve with Newton's method. To make sure no closer point
+/// was missed, the curve is then split into parts until each part's control points bound
+/// it to be no closer than that, within a small tolerance. The result is never more than
+/// the exact distance.
+#[derive(Clone, Copy, Debug)]
+pub struct CubicBezier<T, V> {
+    pub a: V,
+    pub b: V,
+    pub c: V,
+    pub d: V,
+    pub thickness: T,
+}
+
+impl<T, V> CubicBezier<T, V> {
+    pub fn new(a: V, b: V, c: V, d: V, thickness: T) -> Self {
+        CubicBezier {
+            a,
+            b,
+            c,
+            d,
+            thickness,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for CubicBezier<T, V>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        // The curve in power form, `a + (k1 + (k2 + k3 * t) * t) * t`, relative to `p`.
+        let three = T::from(3.0);
+        let a = self.a - p;
+        let k1 = (self.b - self.a) * three;
+        let k2 = (self.a - self.b - self.b + self.c) * three;
+        let k3 = self.d - self.a + (self.b - self.c) * three;
+        let offset = |t: T| a + (k1 + (k2 + k3 * t) * t) * t;
+
+        // Minimize the squared distance, whose derivative is `2 v . v'`, with Newton's method
+        // starting from each sample so that every local minimum is found.
+        let mut best = a.dot(a);
+        for i in 0..=CUBIC_BEZIER_SAMPLES {
+            let mut t = T::from(i as f32 / CUBIC_BEZIER_SAMPLES as f32);
+            for _ in 0..CUBIC_BEZIER_STEPS {
+                let v = offset(t);
+                let d1 = k1 + (k2 + k2 + k3 * (three * t)) * t;
+                let d2 = k2 + k2 + k3 * (three * (t + t));
+                let slope = v.dot(d1);
+                let curvature = d1.dot(d1) + v.dot(d2);
+                // Away from a minimum the step would head uphill, so stay put instead.
+                let step = T::select(curvature.cmp_gt(T::zero()), slope / curvature, T::zero());
+                t = (t - step).clamp(T::zero(), T::one());
+            }
+            let v = offset(t);
+            best = best.min(v.dot(v));
+        }
+        let closest = best.sqrt();
+
+        // Each part of the curve lies within the hull of its control points, so it is no
+        // closer than the distance to its chord minus how far the control points are from
+        // the chord. Split the parts until that bound is close to the closest point found in
+        // every lane, and use the smallest bound.
+        let (b, c, d) = (self.b - p, self.c - p, self.d - p);
+        let tolerance = ((b - a).magnitude() + (c - b).magnitude() + (d - c).magnitude())
+            * T::from(CUBIC_BEZIER_TOLERANCE);
+        let chord_dist2 = |pa: V, ab: V| {
+            T::select(
+                ab.dot(ab).cmp_gt(T::zero()),
+                segment_dist2(pa, ab),
+                pa.dot(pa),
+            )
+        };
+        let half = T::from(0.5);
+        let mut bound = closest;
+        let mut parts = [([a, b, c, d], 0); CUBIC_BEZIER_DEPTH + 1];
+        let mut len = 1;
+        while len > 0 {
+            len -= 1;
+            let ([a, b, c, d], depth) = parts[len];
+            let chord = d - a;
+            let flatness = chord_dist2(b - a, chord).max(chord_dist2(c - a, chord));
+            let part = chord_dist2(-a, chord).sqrt() - flatness.sqrt();
+            if depth == CUBIC_BEZIER_DEPTH || !part.cmp_lt(closest - tolerance).any() {
+                bound = bound.min(part);
+                continue;
+            }
+            // Halve the part with de Casteljau's algorithm.
+            let (ab, bc, cd) = ((a + b) * half, (b + c) * half, (c + d) * half);
+            let (abc, bcd) = ((ab + bc) * half, (bc + cd) * half);
+            let middle = (abc + bcd) * half;
+            parts[len] = ([a, ab, abc, middle], depth + 1);
+            parts[len + 1] = ([middle, bcd, cd, d], depth + 1);
+            len += 2;
+        }
+        bound - self.thickness
+    }
+}
+
+/// An infinite plane with normal `normal`, offset from the origin by `offset` along
+/// the normal. The half-space behind the plane, opposite to the normal, is inside.
+///
+/// `normal` must be normalized for the distance to be exact. In 2D, this is a line
+/// bounding a half-plane.
+#[derive(Clone, Copy, Debug)]
+pub struct Plane<T, V> {
+    pub normal: V,
+    pub offset: T,
+}
+
+impl<T, V> Plane<T, V> {
+    pub fn new(normal: V, offset: T) -> Self {
+        Plane { normal, offset }
+    }
+}
+
+impl<T, V> SDF<T, V> for Plane<T, V>
+where
+    T: Sub<T, Output = T> + Copy,
+    V: Vec<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        p.dot(self.normal) - self.offset
+    }
+}
+
+/// An infinite plane perpendicular to an axis, at `offset` along that axis. The half-space
+/// where the coordinate along `axis` is less than `offset` is inside; use a `Plane` with a
+/// negative normal for the opposite side.
+///
+/// In 2D, this is a line bounding a half-plane, and only `Axis::X` and `Axis::Y` may be used.
+#[derive(Clone, Copy, Debug)]
+pub struct AxisPlane<T, D> {
+    pub axis: Axis,
+    pub offset: T,
+    _pd: PhantomData<D>,
+}
+
+impl<T, D> AxisPlane<T, D> {
+    pub fn new(axis: Axis, offset: T) -> Self {
+        AxisPlane {
+            axis,
+            offset,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for AxisPlane<T, Dim3D>
+where
+    T: Sub<T, Output = T> + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let d = match self.axis {
+            Axis::X => p.x(),
+            Axis::Y => p.y(),
+            Axis::Z => p.z(),
+        };
+        d - self.offset
+    }
+}
+
+impl<T, V> SDF<T, V> for AxisPlane<T, Dim2D>
+where
+    T: Sub<T, Output = T> + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let d = match self.axis {
+            Axis::X => p.x(),
+            Axis::Y => p.y(),
+            Axis::Z => panic!("Attempting to use Z axis for a 2d plane"),
+        };
+        d - self.offset
+    }
+}
+
+/// A cone extending along an axis, with its base of radius `radius` at `-height` and
+/// its tip at `height` along the axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Cone<T> {
+    pub radius: T,
+    pub height: T,
+    pub axis: Axis,
+}
+
+impl<T> Cone<T> {
+    pub fn new(radius: T, height: T, axis: Axis) -> Self {
+        Cone {
+            radius,
+            height,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for Cone<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (r, c) = self.axis.radial(p);
+        // Work relative to the tip, with the cone opening downwards.
+        let w = V::Vec2::new(r, c - self.height);
+        let q = V::Vec2::new(self.radius, -(self.height + self.height));
+        let a = w - q * (w.dot(q) / q.dot(q)).clamp(T::zero(), T::one());
+        let b = w - V::Vec2::new(q.x() * (w.x() / q.x()).clamp(T::zero(), T::one()), q.y());
+        let d = a.dot(a).min(b.dot(b));
+        let s = (w.y() * q.x() - w.x() * q.y()).max(q.y() - w.y());
+        with_sign(d.sqrt(), s.cmp_lt(T::zero()))
+    }
+}
+
+/// A cone with its tip cut off, extending along an axis from `-height` to `height`.
+/// `bottom_radius` is the radius at `-height`, and `top_radius` the radius at `height`.
+#[derive(Clone, Copy, Debug)]
+pub struct CappedCone<T> {
+    pub bottom_radius: T,
+    pub top_radius: T,
+    pub height: T,
+    pub axis: Axis,
+}
+
+impl<T> CappedCone<T> {
+    pub fn new(bottom_radius: T, top_radius: T, height: T, axis: Axis) -> Self {
+        CappedCone {
+            bottom_radius,
+            top_radius,
+            height,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for CappedCone<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (r, c) = self.axis.radial(p);
+        let q = V::Vec2::new(r, c);
+        let k1 = V::Vec2::new(self.top_radius, self.height);
+        let k2 = V::Vec2::new(
+            self.top_radius - self.bottom_radius,
+            self.height + self.height,
+        );
+        let cap_radius = T::select(c.cmp_lt(T::zero()), self.bottom_radius, self.top_radius);
+        let ca = V::Vec2::new(r - r.min(cap_radius), c.max(-c) - self.height);
+        let cb = q - k1 + k2 * ((k1 - q).dot(k2) / k2.dot(k2)).clamp(T::zero(), T::one());
+        let inside = cb.x().cmp_lt(T::zero()) & ca.y().cmp_lt(T::zero());
+        with_sign(ca.dot(ca).min(cb.dot(cb)).sqrt(), inside)
+    }
+}
+
+/// Two spheres along an axis joined by a cone tangent to both. The sphere of radius
+/// `bottom_radius` is centered at `-height` along the axis, and the one of radius
+/// `top_radius` at `height`. If one sphere contains the other, there is no such cone, and
+/// the shape is just the larger sphere.
+#[derive(Clone, Copy, Debug)]
+pub struct RoundCone<T> {
+    pub bottom_radius: T,
+    pub top_radius: T,
+    pub height: T,
+    pub axis: Axis,
+}
+
+impl<T> RoundCone<T> {
+    pub fn new(bottom_radius: T, top_radius: T, height: T, axis: Axis) -> Self {
+        RoundCone {
+            bottom_radius,
+            top_radius,
+            height,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for RoundCone<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (r, c) = self.axis.radial(p);
+        let h = self.height + self.height;
+        let q = V::Vec2::new(r, c + self.height);
+        let b = (self.bottom_radius - self.top_radius) / h;
+        let a = (T::one() - b * b).max(T::zero()).sqrt();
+        let k = q.dot(V::Vec2::new(-b, a));
+        let bottom = q.magnitude() - self.bottom_radius;
+        let top = (q - V::Vec2::new(T::zero(), h)).magnitude() - self.top_radius;
+        let side = q.dot(V::Vec2::new(a, b)) - self.bottom_radius;
+        let cone = T::select(
+            k.cmp_lt(T::zero()),
+            bottom,
+            T::select(k.cmp_gt(a * h), top, side),
+        );
+        // When the radii differ by more than the distance between the centers, one sphere
+        // contains the other.
+        let contained = !(b * b).cmp_lt(T::one());
+        T::select(contained, bottom.min(top), cone)
+    }
+}
+
+/// A spherical sector: the part of a sphere of radius `radius` inside a cone with its tip
+/// at the origin, opening along the positive direction of an axis. `angle` is the angle
+/// between the axis and the side of the cone, in radians.
+#[derive(Clone, Copy, Debug)]
+pub struct SolidAngle<T> {
+    pub angle: T,
+    pub radius: T,
+    pub axis: Axis,
+}
+
+impl<T> SolidAngle<T> {
+    pub fn new(angle: T, radius: T, axis: Axis) -> Self {
+        SolidAngle {
+            angle,
+            radius,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for SolidAngle<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Clamp
+        + SinCos
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (r, c) = self.axis.radial(p);
+        let q = V::Vec2::new(r, c);
+        let (sin, cos) = self.angle.sin_cos();
+        let side = V::Vec2::new(sin, cos);
+        let l = q.magnitude() - self.radius;
+        let m = (q - side * q.dot(side).clamp(T::zero(), self.radius)).magnitude();
+        let outside_cone = (cos * r - sin * c).cmp_gt(T::zero());
+        l.max(with_sign(m, !outside_cone))
+    }
+}
+
+/// An axis-aligned ellipsoid centered at origin, with the radius along each axis given
+/// by the components of `radii`.
+///
+/// Note that the distance returned is a bound rather than exact: it is exact on the surface
+/// and never overestimates the true distance, but may underestimate it elsewhere, which
+/// makes ray marching take a few more steps. See `ExactEllipsoid` for when true distances
+/// are needed.
+#[derive(Clone, Copy, Debug)]
+pub struct Ellipsoid<V> {
+    pub radii: V,
+}
+
+impl<V> Ellipsoid<V> {
+    pub fn new(radii: V) -> Self {
+        Ellipsoid { radii }
+    }
+
+    /// An ellipsoid of the same size which computes exact distances.
+    pub fn exact(self) -> ExactEllipsoid<V> {
+        ExactEllipsoid::new(self.radii)
+    }
+}
+
+impl<T, V> SDF<T, V> for Ellipsoid<V>
+where
+    T: Sub<T, Output = T> + Mul<T, Output = T> + Div<T, Output = T> + One + MaxMin + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let r = self.radii;
+        let k0 = V::new(p.x() / r.x(), p.y() / r.y(), p.z() / r.z()).magnitude();
+        let k1 = V::new(
+            p.x() / (r.x() * r.x()),
+            p.y() / (r.y() * r.y()),
+            p.z() / (r.z() * r.z()),
+        )
+        .magnitude();
+        // The gradient-normalized estimate can overestimate the distance inside the
+        // ellipsoid, where the distance to the unit sphere in scaled space is a safe bound.
+        let r_min = r.x().min(r.y()).min(r.z());
+        (k0 * (k0 - T::one()) / k1).max((k0 - T::one()) * r_min)
+    }
+}
+
+/// An axis-aligned ellipsoid centered at origin, like `Ellipsoid`, but with an exact
+/// distance. The closest point on the ellipsoid is found iteratively by bisection, as
+/// described by [David Eberly](https://www.geometrictools.com/Documentation/DistancePointEllipseEllipsoid.pdf),
+/// which makes this considerably slower than `Ellipsoid`. Since the iteration branches on
+/// every value, it is only implemented for `Real` scalars.
+#[derive(Clone, Copy, Debug)]
+pub struct ExactEllipsoid<V> {
+    pub radii: V,
+}
+
+impl<V> ExactEllipsoid<V> {
+    pub fn new(radii: V) -> Self {
+        ExactEllipsoid { radii }
+    }
+}
+
+impl<T, V> SDF<T, V> for ExactEllipsoid<V>
+where
+    T: Real,
+    V: Vec3<T>,
+{
+    fn dist(&self, p: V) -> T {
+        let radii = [
+            self.radii.x().to_f64(),
+            self.radii.y().to_f64(),
+            self.radii.z().to_f64(),
+        ];
+        let p = [p.x().to_f64(), p.y().to_f64(), p.z().to_f64()];
+
+        // Solve in the first octant with the radii sorted in decreasing order.
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| {
+            radii[b]
+                .partial_cmp(&radii[a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let e = order.map(|i| radii[i]);
+        let y = order.map(|i| p[i].abs());
+        let x = closest_on_ellipsoid(e, y);
+
+        let d = ((x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2) + (x[2] - y[2]).powi(2)).sqrt();
+        let inside = (0..3).map(|i| (y[i] / e[i]).powi(2)).sum::<f64>() < 1.0;
+        T::from_f64(if inside { -d } else { d })
+    }
+}
+
+/// The length of `v`, avoiding overflow.
+fn robust_length(v: &[f64]) -> f64 {
+    let max = v.iter().fold(0.0f64, |max, x| max.max(x.abs()));
+    if max == 0.0 {
+        0.0
+    } else {
+        max * v.iter().map(|x| (x / max).powi(2)).sum::<f64>().sqrt()
+    }
+}
+
+/// Bisects for the root of `sum((n_i / (s + r_i))^2) - 1`, where the root is known to lie
+/// in `[s0, s1]`.
+fn ellipsoid_root(n: &[f64], r: &[f64], mut s0: f64, mut s1: f64) -> f64 {
+    let mut s = s0;
+    for _ in 0..1100 {
+        s = 0.5 * (s0 + s1);
+        if s == s0 || s == s1 {
+            break;
+        }
+        let g = n
+            .iter()
+            .zip(r)
+            .map(|(n, r)| (n / (s + r)).powi(2))
+            .sum::<f64>()
+            - 1.0;
+        if g > 0.0 {
+            s0 = s;
+        } else if g < 0.0 {
+            s1 = s;
+        } else {
+            break;
+        }
+    }
+    s
+}
+
+/// The closest point on an ellipse with radii `e0 >= e1` to the point `y` in the
+/// first quadrant.
+fn closest_on_ellipse([e0, e1]: [f64; 2], [y0, y1]: [f64; 2]) -> [f64; 2] {
+    if y1 > 0.0 {
+        if y0 > 0.0 {
+            let (z0, z1) = (y0 / e0, y1 / e1);
+            let g = z0 * z0 + z1 * z1 - 1.0;
+            if g == 0.0 {
+                return [y0, y1];
+            }
+            let r0 = (e0 / e1).powi(2);
+            let n = [r0 * z0, z1];
+            let s1 = if g < 0.0 {
+                0.0
+            } else {
+                robust_length(&n) - 1.0
+            };
+            let s = ellipsoid_root(&n, &[r0, 1.0], z1 - 1.0, s1);
+            [r0 * y0 / (s + r0), y1 / (s + 1.0)]
+        } else {
+            [0.0, e1]
+        }
+    } else {
+        let (numer0, denom0) = (e0 * y0, e0 * e0 - e1 * e1);
+        if numer0 < denom0 {
+            let xde0 = numer0 / denom0;
+            [e0 * xde0, e1 * (1.0 - xde0 * xde0).sqrt()]
+        } else {
+            [e0, 0.0]
+        }
+    }
+}
+
+/// The closest point on an ellipsoid with radii `e0 >= e1 >= e2` to the point `y` in the
+/// first octant.
+fn closest_on_ellipsoid([e0, e1, e2]: [f64; 3], [y0, y1, y2]: [f64; 3]) -> [f64; 3] {
+    if y2 > 0.0 {
+        if y1 > 0.0 {
+            if y0 > 0.0 {
+                let (z0, z1, z2) = (y0 / e0, y1 / e1, y2 / e2);
+                let g = z0 * z0 + z1 * z1 + z2 * z2 - 1.0;
+                if g == 0.0 {
+                    return [y0, y1, y2];
+                }
+                let (r0, r1) = ((e0 / e2).powi(2), (e1 / e2).powi(2));
+                let n = [r0 * z0, r1 * z1, z2];
+                let s1 = if g < 0.0 {
+                    0.0
+                } else {
+                    robust_length(&n) - 1.0
+                };
+                let s = ellipsoid_root(&n, &[r0, r1, 1.0], z2 - 1.0, s1);
+                [r0 * y0 / (s + r0), r1 * y1 / (s + r1), y2 / (s + 1.0)]
+            } else {
+                let [x1, x2] = closest_on_ellipse([e1, e2], [y1, y2]);
+                [0.0, x1, x2]
+            }
+        } else if y0 > 0.0 {
+            let [x0, x2] = closest_on_ellipse([e0, e2], [y0, y2]);
+            [x0, 0.0, x2]
+        } else {
+            [0.0, 0.0, e2]
+        }
+    } else {
+        let (denom0, denom1) = (e0 * e0 - e2 * e2, e1 * e1 - e2 * e2);
+        let (numer0, numer1) = (e0 * y0, e1 * y1);
+        if numer0 < denom0 && numer1 < denom1 {
+            let (xde0, xde1) = (numer0 / denom0, numer1 / denom1);
+            let discr = 1.0 - xde0 * xde0 - xde1 * xde1;
+            if discr > 0.0 {
+                return [e0 * xde0, e1 * xde1, e2 * discr.sqrt()];
+            }
+        }
+        let [x0, x1] = closest_on_ellipse([e0, e1], [y0, y1]);
+        [x0, x1, 0.0]
+    }
+}
+
+/// A regular octahedron centered at origin, with its vertices at `size` along each axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Octahedron<T> {
+    pub size: T,
+}
+
+impl<T> Octahedron<T> {
+    pub fn new(size: T) -> Self {
+        Octahedron { size }
+    }
+}
+
+impl<T, V> SDF<T, V> for Octahedron<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Zero
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = p.abs();
+        let m = p.x() + p.y() + p.z() - self.size;
+        let three = T::from(3.0);
+        // Find the face whose region contains the point, if it isn't closest to the
+        // plane of the face itself.
+        let near_x = (three * p.x()).cmp_lt(m);
+        let near_y = (three * p.y()).cmp_lt(m);
+        let near_z = (three * p.z()).cmp_lt(m);
+        let select3 = |mask, a: (T, T, T), b: (T, T, T)| {
+            (
+                T::select(mask, a.0, b.0),
+                T::select(mask, a.1, b.1),
+                T::select(mask, a.2, b.2),
+            )
+        };
+        let q = select3(
+            near_x,
+            (p.x(), p.y(), p.z()),
+            select3(near_y, (p.y(), p.z(), p.x()), (p.z(), p.x(), p.y())),
+        );
+        let k = ((q.2 - q.1 + self.size) * T::point_five()).clamp(T::zero(), self.size);
+        let edge = V::new(q.0, q.1 - self.size + k, q.2 - k).magnitude();
+        let face = m * T::from(0.577_350_26);
+        T::select(near_x | near_y | near_z, edge, face)
+    }
+}
+
+/// Extrudes the 2D distance `d` symmetrically along an axis, to a half-height of `height`.
+#[inline]
+pub(crate) fn extrude<T, V>(d: T, c: T, height: T) -> T
+where
+    T: Sub<T, Output = T> + Add<T, Output = T> + Neg<Output = T> + Zero + MaxMin + Copy,
+    V: Vec2<T>,
+{
+    let w = V::new(d, c.max(-c) - height);
+    w.x().max(w.y()).min(T::zero()) + w.max(V::zero()).magnitude()
+}
+
+/// A hexagonal prism extending along an axis from `-height` to `height`, with the distance
+/// from its axis to the middle of its sides given by `radius`.
+#[derive(Clone, Copy, Debug)]
+pub struct HexPrism<T> {
+    pub radius: T,
+    pub height: T,
+    pub axis: Axis,
+}
+
+impl<T> HexPrism<T> {
+    pub fn new(radius: T, height: T, axis: Axis) -> Self {
+        HexPrism {
+            radius,
+            height,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for HexPrism<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Clamp
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (a, b, c) = self.axis.split(p);
+        let k = V::Vec2::new(T::from(-0.866_025_4), T::from(0.5));
+        let k_z = T::from(0.577_350_26);
+        let mut q = V::Vec2::new(a, b).abs();
+        q = q - k * (T::from(2.0) * k.dot(q).min(T::zero()));
+        let edge = q - V::Vec2::new(
+            q.x().clamp(-k_z * self.radius, k_z * self.radius),
+            self.radius,
+        );
+        let d = with_sign(edge.magnitude(), (q.y() - self.radius).cmp_lt(T::zero()));
+        extrude::<T, V::Vec2>(d, c, self.height)
+    }
+}
+
+/// A triangular prism extending along an axis from `-height` to `height`. Its cross section
+/// is an equilateral triangle with sides of length `2 * size` centered on the axis, with a
+/// flat side at the bottom and the opposite vertex pointing up, along the second coordinate
+/// perpendicular to the axis as ordered by `CappedCylinder`.
+#[derive(Clone, Copy, Debug)]
+pub struct TriPrism<T> {
+    pub size: T,
+    pub height: T,
+    pub axis: Axis,
+}
+
+impl<T> TriPrism<T> {
+    pub fn new(size: T, height: T, axis: Axis) -> Self {
+        TriPrism { size, height, axis }
+    }
+}
+
+impl<T, V> SDF<T, V> for TriPrism<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (a, b, c) = self.axis.split(p);
+        let k = T::from(1.732_050_8);
+        let x = a.max(-a) - self.size;
+        let y = b + self.size / k;
+        // Reflect points beyond the right side onto it.
+        let reflect = (x + k * y).cmp_gt(T::zero());
+        let (x, y) = (
+            T::select(reflect, (x - k * y) * T::point_five(), x),
+            T::select(reflect, (-k * x - y) * T::point_five(), y),
+        );
+        let x = x - x.clamp(-(self.size + self.size), T::zero());
+        let d = with_sign(V::Vec2::new(x, y).magnitude(), y.cmp_gt(T::zero()));
+        extrude::<T, V::Vec2>(d, c, self.height)
+    }
+}
+
+/// A pyramid with a square base of half-width `base` centered at origin, and its apex at
+/// `height` along an axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Pyramid<T> {
+    pub base: T,
+    pub height: T,
+    pub axis: Axis,
+}
+
+impl<T> Pyramid<T> {
+    pub fn new(base: T, height: T, axis: Axis) -> Self {
+        Pyramid { base, height, axis }
+    }
+}
+
+impl<T, V> SDF<T, V> for Pyramid<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        // Work with a pyramid whose base has sides of length one.
+        let scale = self.base + self.base;
+        let (a, b, c) = self.axis.split(p);
+        let (a, b, y) = (a / scale, b / scale, c / scale);
+        let h = self.height / scale;
+        let half = T::point_five();
+        let quarter = T::from(0.25);
+
+        let m2 = h * h + quarter;
+        let (a, b) = (a.max(-a), b.max(-b));
+        let swap = b.cmp_gt(a);
+        let x = T::select(swap, b, a) - half;
+        let z = T::select(swap, a, b) - half;
+        let q = V::new(z, h * y - half * x, h * x + half * y);
+
+        let s = (-q.x()).max(T::zero());
+        let t = ((q.y() - half * z) / (m2 + quarter)).clamp(T::zero(), T::one());
+        let a = m2 * (q.x() + s) * (q.x() + s) + q.y() * q.y();
+        let b = m2 * (q.x() + half * t) * (q.x() + half * t) + (q.y() - m2 * t) * (q.y() - m2 * t);
+        let above = q.y().min(-q.x() * m2 - q.y() * half).cmp_gt(T::zero());
+        let d2 = T::select(above, T::zero(), a.min(b));
+        let side = ((d2 + q.z() * q.z()) / m2).sqrt();
+        let base = V::new(x.max(T::zero()), y, z.max(T::zero())).magnitude();
+        with_sign(side.min(base), q.z().max(-y).cmp_lt(T::zero())) * scale
+    }
+}
+
+/// The distance bound of a polyhedron made from the planes perpendicular to `normals` and
+/// their opposites, at `radius` from the origin.
+#[inline]
+fn polyhedron<T, V>(p: V, normals: &[(f32, f32, f32)], radius: T) -> T
+where
+    T: Sub<T, Output = T> + Neg<Output = T> + Zero + MaxMin + From<f32> + Copy,
+    V: Vec3<T>,
+{
+    let mut d = T::zero();
+    for &(x, y, z) in normals {
+        let n = p.dot(V::new(T::from(x), T::from(y), T::from(z)));
+        d = d.max(n).max(-n);
+    }
+    d - radius
+}
+
+/// A regular dodecahedron centered at origin, with the distance from its center to the
+/// middle of its faces given by `radius`.
+///
+/// Note that the distance returned is a bound rather than exact outside of the dodecahedron,
+/// since it is the distance to the furthest of the planes its faces lie on.
+#[derive(Clone, Copy, Debug)]
+pub struct Dodecahedron<T> {
+    pub radius: T,
+}
+
+impl<T> Dodecahedron<T> {
+    pub fn new(radius: T) -> Self {
+        Dodecahedron { radius }
+    }
+}
+
+impl<T, V> SDF<T, V> for Dodecahedron<T>
+where
+    T: Sub<T, Output = T> + Neg<Output = T> + Zero + MaxMin + From<f32> + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        // Cyclic permutations of (0, phi, 1), normalized.
+        const A: f32 = 0.850_650_8;
+        const B: f32 = 0.525_731_1;
+        let normals = [
+            (0.0, A, B),
+            (0.0, -A, B),
+            (B, 0.0, A),
+            (-B, 0.0, A),
+            (A, B, 0.0),
+            (-A, B, 0.0),
+        ];
+        polyhedron(p, &normals, self.radius)
+    }
+}
+
+/// A regular icosahedron centered at origin, with the distance from its center to the
+/// middle of its faces given by `radius`.
+///
+/// Note that the distance returned is a bound rather than exact outside of the icosahedron,
+/// since it is the distance to the furthest of the planes its faces lie on.
+#[derive(Clone, Copy, Debug)]
+pub struct Icosahedron<T> {
+    pub radius: T,
+}
+
+impl<T> Icosahedron<T> {
+    pub fn new(radius: T) -> Self {
+        Icosahedron { radius }
+    }
+}
+
+impl<T, V> SDF<T, V> for Icosahedron<T>
+where
+    T: Sub<T, Output = T> + Neg<Output = T> + Zero + MaxMin + From<f32> + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        // (±1, ±1, ±1) and the cyclic permutations of (0, 1 / phi, phi), normalized.
+        const A: f32 = 0.577_350_26;
+        const B: f32 = 0.356_822_1;
+        const C: f32 = 0.934_172_4;
+        let normals = [
+            (A, A, A),
+            (-A, A, A),
+            (A, -A, A),
+            (A, A, -A),
+            (0.0, B, C),
+            (0.0, -B, C),
+            (C, 0.0, B),
+            (-C, 0.0, B),
+            (B, C, 0.0),
+            (-B, C, 0.0),
+        ];
+        polyhedron(p, &normals, self.radius)
+    }
+}
+
+/// The edges of a box centered at origin with axis-aligned dimensions, made into bars
+/// with a square cross section of half-width `thickness`.
+#[derive(Clone, Copy, Debug)]
+pub struct BoxFrame<T, V> {
+    pub dims: V,
+    pub thickness: T,
+}
+
+impl<T, V> BoxFrame<T, V> {
+    pub fn new(dims: V, thickness: T) -> Self {
+        BoxFrame { dims, thickness }
+    }
+}
+
+impl<T, V> SDF<T, V> for BoxFrame<T, V>
+where
+    T: Add<T, Output = T> + Sub<T, Output = T> + Zero + MaxMin + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let e = V::new(self.thickness, self.thickness, self.thickness);
+        let p = p.abs() - self.dims;
+        let q = (p + e).abs() - e;
+        let bar = |x: T, y: T, z: T| {
+            V::new(x, y, z).max(V::zero()).magnitude() + x.max(y.max(z)).min(T::zero())
+        };
+        bar(p.x(), q.y(), q.z())
+            .min(bar(q.x(), p.y(), q.z()))
+            .min(bar(q.x(), q.y(), p.z()))
+    }
+}
+
+/// A chain link that sits on the XZ plane: a torus with major radius `radius` and tube
+/// radius `thickness`, whose two halves are pulled apart along the X axis by `length` each,
+/// with straight tubes joining them.
+#[derive(Clone, Copy, Debug)]
+pub struct Link<T> {
+    pub length: T,
+    pub radius: T,
+    pub thickness: T,
+}
+
+impl<T> Link<T> {
+    pub fn new(length: T, radius: T, thickness: T) -> Self {
+        Link {
+            length,
+            radius,
+            thickness,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for Link<T>
+where
+    T: Sub<T, Output = T> + Neg<Output = T> + Zero + MaxMin + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let x = (p.x().max(-p.x()) - self.length).max(T::zero());
+        let q = V::Vec2::new(V::Vec2::new(x, p.z()).magnitude() - self.radius, p.y());
+        q.magnitude() - self.thickness
+    }
+}
+
+/// An arc of a torus that sits on the XZ plane, with major radius `radius` and tube radius
+/// `thickness`. The arc is symmetric around the positive Z axis and spans an angle of
+/// `aperture` to either side of it, which is given by its sine and cosine.
+#[derive(Clone, Copy, Debug)]
+pub struct CappedTorus<T> {
+    pub aperture_sin: T,
+    pub aperture_cos: T,
+    pub radius: T,
+    pub thickness: T,
+}
+
+impl<T> CappedTorus<T> {
+    pub fn new(aperture_sin: T, aperture_cos: T, radius: T, thickness: T) -> Self {
+        CappedTorus {
+            aperture_sin,
+            aperture_cos,
+            radius,
+            thickness,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for CappedTorus<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Neg<Output = T>
+        + MaxMin
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let x = p.x().max(-p.x());
+        let (sin, cos) = (self.aperture_sin, self.aperture_cos);
+        // Past the ends of the arc, the closest point of its center line is the end itself.
+        let past_end = (cos * x).cmp_gt(sin * p.z());
+        let k = T::select(
+            past_end,
+            x * sin + p.z() * cos,
+            V::Vec2::new(x, p.z()).magnitude(),
+        );
+        (p.dot(p) + self.radius * self.radius - (self.radius + self.radius) * k).sqrt()
+            - self.thickness
+    }
+}
+
+/// The squared distance to a segment from a point, given the offset `pa` of the point from
+/// the start of the segment, and the offset `ab` of the end of the segment from its start.
+#[inline]
+pub(crate) fn segment_dist2<T, V>(pa: V, ab: V) -> T
+where
+    T: Div<T, Output = T> + Zero + One + Clamp,
+    V: Vec<T>,
+{
+    let d = ab * (ab.dot(pa) / ab.dot(ab)).clamp(T::zero(), T::one()) - pa;
+    d.dot(d)
+}
+
+/// A triangle with corners `a`, `b` and `c`, in either winding order.
+///
+/// In 3D, the distance is unsigned since the triangle has no volume; use `SDF::round` to
+/// give it a thickness. In 2D, it is negative inside the triangle.
+#[derive(Clone, Copy, Debug)]
+pub struct Triangle<V, D> {
+    pub a: V,
+    pub b: V,
+    pub c: V,
+    _pd: PhantomData<D>,
+}
+
+impl<V, D> Triangle<V, D> {
+    pub fn new(a: V, b: V, c: V) -> Self {
+        Triangle {
+            a,
+            b,
+            c,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for Triangle<V, Dim3D>
+where
+    T: Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (ba, pa) = (self.b - self.a, p - self.a);
+        let (cb, pb) = (self.c - self.b, p - self.b);
+        let (ac, pc) = (self.a - self.c, p - self.c);
+        let nor = ba.cross(ac);
+        // Outside of the prism above and below the triangle, the closest point is on
+        // one of its edges.
+        let outside = ba.cross(nor).dot(pa).cmp_lt(T::zero())
+            | cb.cross(nor).dot(pb).cmp_lt(T::zero())
+            | ac.cross(nor).dot(pc).cmp_lt(T::zero());
+        let edges = segment_dist2(pa, ba)
+            .min(segment_dist2(pb, cb))
+            .min(segment_dist2(pc, ac));
+        let face = nor.dot(pa) * nor.dot(pa) / nor.dot(nor);
+        T::select(outside, edges, face).sqrt()
+    }
+}
+
+/// A planar quad with corners `a`, `b`, `c` and `d` in order around it.
+///
+/// The distance is unsigned since the quad has no volume; use `SDF::round` to give it a
+/// thickness.
+#[derive(Clone, Copy, Debug)]
+pub struct Quad<V> {
+    pub a: V,
+    pub b: V,
+    pub c: V,
+    pub d: V,
+}
+
+impl<V> Quad<V> {
+    pub fn new(a: V, b: V, c: V, d: V) -> Self {
+        Quad { a, b, c, d }
+    }
+}
+
+impl<T, V> SDF<T, V> for Quad<V>
+where
+    T: Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (ba, pa) = (self.b - self.a, p - self.a);
+        let (cb, pb) = (self.c - self.b, p - self.b);
+        let (dc, pc) = (self.d - self.c, p - self.c);
+        let (ad, pd) = (self.a - self.d, p - self.d);
+        let nor = ba.cross(ad);
+        let outside = ba.cross(nor).dot(pa).cmp_lt(T::zero())
+            | cb.cross(nor).dot(pb).cmp_lt(T::zero())
+            | dc.cross(nor).dot(pc).cmp_lt(T::zero())
+            | ad.cross(nor).dot(pd).cmp_lt(T::zero());
+        let edges = segment_dist2(pa, ba)
+            .min(segment_dist2(pb, cb))
+            .min(segment_dist2(pc, dc))
+            .min(segment_dist2(pd, ad));
+        let face = nor.dot(pa) * nor.dot(pa) / nor.dot(nor);
+        T::select(outside, edges, face).sqrt()
+    }
+}
+
+/// A sphere of radius `radius` centered at origin, cut by a plane perpendicular to an axis
+/// at `height` along it, keeping the part beyond the plane. `height` must be between
+/// `-radius` and `radius`.
+#[derive(Clone, Copy, Debug)]
+pub struct CutSphere<T> {
+    pub radius: T,
+    pub height: T,
+    pub axis: Axis,
+}
+
+impl<T> CutSphere<T> {
+    pub fn new(radius: T, height: T, axis: Axis) -> Self {
+        CutSphere {
+            radius,
+            height,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for CutSphere<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Zero
+        + MaxMin
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (r, h) = (self.radius, self.height);
+        let w = (r * r - h * h).sqrt();
+        let (x, y) = self.axis.radial(p);
+        let q = V::Vec2::new(x, y);
+        let s = ((h - r) * x * x + w * w * (h + r - (y + y))).max(h * x - w * y);
+        let sphere = q.magnitude() - r;
+        let cap = h - y;
+        let rim = (q - V::Vec2::new(w, h)).magnitude();
+        T::select(
+            s.cmp_lt(T::zero()),
+            sphere,
+            T::select(x.cmp_lt(w), cap, rim),
+        )
+    }
+}
+
+/// A hollow sphere of radius `radius` centered at origin, with walls of half-thickness
+/// `thickness`, cut by a plane perpendicular to an axis at `height` along it. The part
+/// before the plane is kept, which makes a bowl opening along the axis. `height` must be
+/// between `-radius` and `radius`.
+#[derive(Clone, Copy, Debug)]
+pub struct CutHollowSphere<T> {
+    pub radius: T,
+    pub height: T,
+    pub thickness: T,
+    pub axis: Axis,
+}
+
+impl<T> CutHollowSphere<T> {
+    pub fn new(radius: T, height: T, thickness: T, axis: Axis) -> Self {
+        CutHollowSphere {
+            radius,
+            height,
+            thickness,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for CutHollowSphere<T>
+where
+    T: Sub<T, Output = T> + Mul<T, Output = T> + Neg<Output = T> + MaxMin + Sqrt + Compare + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (r, h) = (self.radius, self.height);
+        let w = (r * r - h * h).sqrt();
+        let (x, y) = self.axis.radial(p);
+        let q = V::Vec2::new(x, y);
+        let shell = q.magnitude() - r;
+        let rim = (q - V::Vec2::new(w, h)).magnitude();
+        T::select((h * x).cmp_lt(w * y), rim, shell.max(-shell)) - self.thickness
+    }
+}
+
+/// A sphere of radius `radius` centered at origin with a spherical bite taken out of it.
+/// The bite is a sphere of radius `bite_radius` centered at `offset` along an axis.
+#[derive(Clone, Copy, Debug)]
+pub struct DeathStar<T> {
+    pub radius: T,
+    pub bite_radius: T,
+    pub offset: T,
+    pub axis: Axis,
+}
+
+impl<T> DeathStar<T> {
+    pub fn new(radius: T, bite_radius: T, offset: T, axis: Axis) -> Self {
+        DeathStar {
+            radius,
+            bite_radius,
+            offset,
+            axis,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for DeathStar<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (ra, rb, d) = (self.radius, self.bite_radius, self.offset);
+        // The rim where the two spheres intersect, as a point in the plane through the axis.
+        let a = (ra * ra - rb * rb + d * d) / (d + d);
+        let b = (ra * ra - a * a).max(T::zero()).sqrt();
+        let (y, x) = self.axis.radial(p);
+        let q = V::Vec2::new(x, y);
+        let near_rim = (x * b - y * a).cmp_gt(d * (b - y).max(T::zero()));
+        let rim = (q - V::Vec2::new(a, b)).magnitude();
+        let carved = (q.magnitude() - ra).max(rb - (q - V::Vec2::new(d, T::zero())).magnitude());
+        T::select(near_rim, rim, carved)
+    }
+}
+
+impl<T, V> SDF<T, V> for Triangle<V, Dim2D>
+where
+    T: Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (e0, e1, e2) = (self.b - self.a, self.c - self.b, self.a - self.c);
+        let (v0, v1, v2) = (p - self.a, p - self.b, p - self.c);
+        let cross = |a: V, b: V| a.x() * b.y() - a.y() * b.x();
+        // Flip the side of each edge for clockwise triangles.
+        let orientation = cross(e0, e2);
+        let side = |v: V, e: V| with_sign(cross(v, e), orientation.cmp_lt(T::zero()));
+        let d = V::new(segment_dist2(v0, e0), side(v0, e0))
+            .min(V::new(segment_dist2(v1, e1), side(v1, e1)))
+            .min(V::new(segment_dist2(v2, e2), side(v2, e2)));
+        with_sign(d.x().sqrt(), d.y().cmp_gt(T::zero()))
+    }
+}
+
+/// A 2D line segment from `a` to `b`. The distance is unsigned; see `Line` for one
+/// with thickness.
+#[derive(Clone, Copy, Debug)]
+pub struct Segment<V> {
+    pub a: V,
+    pub b: V,
+}
+
+impl<V> Segment<V> {
+    pub fn new(a: V, b: V) -> Self {
+        Segment { a, b }
+    }
+}
+
+impl<T, V> SDF<T, V> for Segment<V>
+where
+    T: Div<T, Output = T> + Zero + One + Clamp + Sqrt + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        segment_dist2(p - self.a, self.b - self.a).sqrt()
+    }
+}
+
+/// A 2D box centered at origin with axis-aligned dimensions and rounded corners. The
+/// radius of each corner is given by `radii`, counter-clockwise starting from the corner
+/// with positive x and y.
+#[derive(Clone, Copy, Debug)]
+pub struct RoundedBox<T, V> {
+    pub dims: V,
+    pub radii: [T; 4],
+}
+
+impl<T, V> RoundedBox<T, V> {
+    pub fn new(dims: V, radii: [T; 4]) -> Self {
+        RoundedBox { dims, radii }
+    }
+}
+
+impl<T, V> SDF<T, V> for RoundedBox<T, V>
+where
+    T: Add<T, Output = T> + Sub<T, Output = T> + Zero + MaxMin + Compare + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let [top_right, top_left, bottom_left, bottom_right] = self.radii;
+        let right = p.x().cmp_gt(T::zero());
+        let r = T::select(
+            p.y().cmp_gt(T::zero()),
+            T::select(right, top_right, top_left),
+            T::select(right, bottom_right, bottom_left),
+        );
+        let q = p.abs() - self.dims + V::new(r, r);
+        q.x().max(q.y()).min(T::zero()) + q.max(V::zero()).magnitude() - r
+    }
+}
+
+/// A rhombus centered at origin, with its corners at `dims.x()` along the X axis and
+/// `dims.y()` along the Y axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Rhombus<V> {
+    pub dims: V,
+}
+
+impl<V> Rhombus<V> {
+    pub fn new(dims: V) -> Self {
+        Rhombus { dims }
+    }
+}
+
+impl<T, V> SDF<T, V> for Rhombus<V>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + PointFive
+        + Clamp
+        + Compare
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let b = self.dims;
+        let p = p.abs();
+        let e = b - p - p;
+        let h = ((e.x() * b.x() - e.y() * b.y()) / b.dot(b)).clamp(-T::one(), T::one());
+        let closest = V::new(b.x() * (T::one() - h), b.y() * (T::one() + h)) * T::point_five();
+        let side = p.x() * b.y() + p.y() * b.x() - b.x() * b.y();
+        with_sign((p - closest).magnitude(), side.cmp_lt(T::zero()))
+    }
+}
+
+/// An isosceles trapezoid centered at origin, extending from `-height` to `height` along
+/// the Y axis. `bottom_width` and `top_width` are the half-widths of its bottom and top
+/// sides.
+#[derive(Clone, Copy, Debug)]
+pub struct IsoscelesTrapezoid<T> {
+    pub bottom_width: T,
+    pub top_width: T,
+    pub height: T,
+}
+
+impl<T> IsoscelesTrapezoid<T> {
+    pub fn new(bottom_width: T, top_width: T, height: T) -> Self {
+        IsoscelesTrapezoid {
+            bottom_width,
+            top_width,
+            height,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for IsoscelesTrapezoid<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = V::new(p.x().max(-p.x()), p.y());
+        let k1 = V::new(self.top_width, self.height);
+        let k2 = V::new(
+            self.top_width - self.bottom_width,
+            self.height + self.height,
+        );
+        let width = T::select(p.y().cmp_lt(T::zero()), self.bottom_width, self.top_width);
+        let ca = V::new(p.x() - p.x().min(width), p.y().max(-p.y()) - self.height);
+        let cb = p - k1 + k2 * ((k1 - p).dot(k2) / k2.dot(k2)).clamp(T::zero(), T::one());
+        let inside = cb.x().cmp_lt(T::zero()) & ca.y().cmp_lt(T::zero());
+        with_sign(ca.dot(ca).min(cb.dot(cb)).sqrt(), inside)
+    }
+}
+
+/// A parallelogram centered at origin, with horizontal top and bottom sides of half-width
+/// `width` at `height` and `-height` along the Y axis. The top side is shifted by `skew`
+/// along the X axis, and the bottom side by `-skew`.
+#[derive(Clone, Copy, Debug)]
+pub struct Parallelogram<T> {
+    pub width: T,
+    pub height: T,
+    pub skew: T,
+}
+
+impl<T> Parallelogram<T> {
+    pub fn new(width: T, height: T, skew: T) -> Self {
+        Parallelogram {
+            width,
+            height,
+            skew,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for Parallelogram<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (wi, he) = (self.width, self.height);
+        let e = V::new(self.skew, he);
+        let flip = |mask, p: V| V::new(with_sign(p.x(), mask), with_sign(p.y(), mask));
+        let p = flip(p.y().cmp_lt(T::zero()), p);
+        // Distance to the top side.
+        let w = p - e;
+        let w = V::new(w.x() - w.x().clamp(-wi, wi), w.y());
+        let top = V::new(w.dot(w), -w.y());
+        // Distance to the right side.
+        let s = p.x() * e.y() - p.y() * e.x();
+        let p = flip(s.cmp_lt(T::zero()), p);
+        let v = p - V::new(wi, T::zero());
+        let v = v - e * (v.dot(e) / e.dot(e)).clamp(-T::one(), T::one());
+        let d = top.min(V::new(v.dot(v), wi * he - s.max(-s)));
+        with_sign(d.x().sqrt(), d.y().cmp_gt(T::zero()))
+    }
+}
+
+/// Folds `p` into the sector of a shape with `2 * n` fold symmetry around the origin, where
+/// `angle` is `PI / n`. The result is expressed in the frame of the sector, with the first
+/// coordinate along the bisector of the sector and the second perpendicular to it, and the
+/// first sector is centered at `offset` radians clockwise from the Y axis.
+#[inline]
+fn fold_sector<T, V>(p: V, angle: T, offset: T) -> V
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + MaxMin
+        + SinCos
+        + Atan2
+        + RoundNearest
+        + Copy,
+    V: Vec2<T>,
+{
+    let sector = angle + angle;
+    let theta = p.x().atan2(p.y()) - offset;
+    let (sin, cos) = (theta - sector * (theta / sector).round_nearest()).sin_cos();
+    V::new(cos, sin.max(-sin)) * p.magnitude()
+}
+
+/// A regular polygon centered at origin, with `sides` sides and its corners at `radius`
+/// from the origin. One of its sides is at the top, parallel to the X axis. There must be
+/// at least three sides.
+///
+/// See `Pentagon`, `Hexagon` and `Octagon` for faster versions with a fixed number of sides.
+#[derive(Clone, Copy, Debug)]
+pub struct RegularPolygon<T> {
+    pub radius: T,
+    pub sides: usize,
+}
+
+impl<T> RegularPolygon<T> {
+    /// # Panics
+    ///
+    /// Panics if `sides` is less than 3.
+    pub fn new(radius: T, sides: usize) -> Self {
+        assert!(sides >= 3, "RegularPolygon must have at least 3 sides");
+        RegularPolygon { radius, sides }
+    }
+}
+
+impl<T, V> SDF<T, V> for RegularPolygon<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Clamp
+        + SinCos
+        + Atan2
+        + RoundNearest
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let angle = T::from(std::f32::consts::PI / self.sides as f32);
+        let (sin, cos) = angle.sin_cos();
+        let p = fold_sector(p, angle, T::zero()) - V::new(cos, sin) * self.radius;
+        let p = V::new(p.x(), p.y() + (-p.y()).clamp(T::zero(), self.radius * sin));
+        with_sign(p.magnitude(), p.x().cmp_lt(T::zero()))
+    }
+}
+
+/// A star centered at origin with `points` points at `radius` from the origin, one of
+/// which points up along the Y axis. The corners between the points are at `inner_ratio`
+/// times `radius` from the origin. There must be at least two points.
+#[derive(Clone, Copy, Debug)]
+pub struct Star<T> {
+    pub radius: T,
+    pub points: usize,
+    pub inner_ratio: T,
+}
+
+impl<T> Star<T> {
+    /// # Panics
+    ///
+    /// Panics if `points` is less than 2.
+    pub fn new(radius: T, points: usize, inner_ratio: T) -> Self {
+        assert!(points >= 2, "Star must have at least 2 points");
+        Star {
+            radius,
+            points,
+            inner_ratio,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for Star<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Clamp
+        + SinCos
+        + Atan2
+        + RoundNearest
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let angle = T::from(std::f32::consts::PI / self.points as f32);
+        let (sin, cos) = angle.sin_cos();
+        // The direction of the edge from the tip of a point to the inner corner next to it.
+        let (edge_sin, edge_cos) = sin.atan2(cos - self.inner_ratio).sin_cos();
+        let edge = V::new(edge_cos, edge_sin);
+        let p = fold_sector(p, angle, angle) - V::new(cos, sin) * self.radius;
+        let p = p + edge * (-p.dot(edge)).clamp(T::zero(), self.radius * sin / edge_sin);
+        with_sign(p.magnitude(), p.x().cmp_lt(T::zero()))
+    }
+}
+
+/// Reflects `p` across the line through the origin perpendicular to `n` if it lies behind it.
+#[inline]
+fn reflect_behind<T, V>(p: V, n: V) -> V
+where
+    T: Add<T, Output = T> + Mul<T, Output = T> + Zero + MaxMin + Copy,
+    V: Vec2<T>,
+{
+    let d = n.dot(p).min(T::zero());
+    p - n * (d + d)
+}
+
+/// The distance to a regular polygon folded so that `p` is closest to its top side, at
+/// `apothem` from the origin with corners at `half_side` to either side.
+#[inline]
+fn top_side<T, V>(p: V, apothem: T, half_side: T) -> T
+where
+    T: Sub<T, Output = T> + Neg<Output = T> + Zero + MaxMin + Clamp + Compare + Copy,
+    V: Vec2<T>,
+{
+    let d = p - V::new(p.x().clamp(-half_side, half_side), apothem);
+    with_sign(d.magnitude(), d.y().cmp_lt(T::zero()))
+}
+
+/// A regular pentagon centered at origin, with its corners at `radius` from the origin.
+/// One of its sides is at the top, parallel to the X axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Pentagon<T> {
+    pub radius: T,
+}
+
+impl<T> Pentagon<T> {
+    pub fn new(radius: T) -> Self {
+        Pentagon { radius }
+    }
+}
+
+impl<T, V> SDF<T, V> for Pentagon<T>
+where
+    T: Add<T, Output = T>
+        + Mul<T, Output = T>
+        + Sub<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Clamp
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        // cos(PI / 5) and sin(PI / 5).
+        let (cos, sin) = (T::from(0.809_017), T::from(0.587_785_24));
+        let p = V::new(p.x().max(-p.x()), p.y());
+        let p = reflect_behind(p, V::new(-cos, sin));
+        let p = reflect_behind(p, V::new(cos, sin));
+        top_side(p, self.radius * cos, self.radius * sin)
+    }
+}
+
+/// A regular hexagon centered at origin, with its corners at `radius` from the origin.
+/// Two of its sides are parallel to the X axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Hexagon<T> {
+    pub radius: T,
+}
+
+impl<T> Hexagon<T> {
+    pub fn new(radius: T) -> Self {
+        Hexagon { radius }
+    }
+}
+
+impl<T, V> SDF<T, V> for Hexagon<T>
+where
+    T: Add<T, Output = T>
+        + Mul<T, Output = T>
+        + Sub<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        // cos(PI / 6) and sin(PI / 6).
+        let (cos, sin) = (T::from(0.866_025_4), T::point_five());
+        let p = reflect_behind(p.abs(), V::new(-cos, sin));
+        top_side(p, self.radius * cos, self.radius * sin)
+    }
+}
+
+/// A regular octagon centered at origin, with its corners at `radius` from the origin.
+/// Four of its sides are parallel to the X or Y axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Octagon<T> {
+    pub radius: T,
+}
+
+impl<T> Octagon<T> {
+    pub fn new(radius: T) -> Self {
+        Octagon { radius }
+    }
+}
+
+impl<T, V> SDF<T, V> for Octagon<T>
+where
+    T: Add<T, Output = T>
+        + Mul<T, Output = T>
+        + Sub<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Clamp
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        // cos(PI / 8) and sin(PI / 8).
+        let (cos, sin) = (T::from(0.923_879_5), T::from(0.382_683_43));
+        let p = reflect_behind(p.abs(), V::new(-cos, sin));
+        let p = reflect_behind(p, V::new(cos, sin));
+        top_side(p, self.radius * cos, self.radius * sin)
+    }
+}
+
+/// A 2D arc of a circle with radius `radius` and thickness `thickness`. The arc is symmetric
+/// around the positive Y axis and spans an angle of `aperture` to either side of it, which
+/// is given by its sine and cosine.
+#[derive(Clone, Copy, Debug)]
+pub struct Arc<T> {
+    pub aperture_sin: T,
+    pub aperture_cos: T,
+    pub radius: T,
+    pub thickness: T,
+}
+
+impl<T> Arc<T> {
+    pub fn new(aperture_sin: T, aperture_cos: T, radius: T, thickness: T) -> Self {
+        Arc {
+            aperture_sin,
+            aperture_cos,
+            radius,
+            thickness,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for Arc<T>
+where
+    T: Sub<T, Output = T> + Mul<T, Output = T> + Neg<Output = T> + MaxMin + Compare + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = V::new(p.x().max(-p.x()), p.y());
+        let (sin, cos) = (self.aperture_sin, self.aperture_cos);
+        // Past the ends of the arc, the closest point of its center line is the end itself.
+        let past_end = (cos * p.x()).cmp_gt(sin * p.y());
+        let ring = p.magnitude() - self.radius;
+        let end = (p - V::new(sin, cos) * self.radius).magnitude();
+        T::select(past_end, end, ring.max(-ring)) - self.thickness
+    }
+}
+
+/// A 2D circular sector with radius `radius`. The sector is symmetric around the positive Y
+/// axis and spans an angle of `aperture` to either side of it, which is given by its sine
+/// and cosine.
+#[derive(Clone, Copy, Debug)]
+pub struct Pie<T> {
+    pub aperture_sin: T,
+    pub aperture_cos: T,
+    pub radius: T,
+}
+
+impl<T> Pie<T> {
+    pub fn new(aperture_sin: T, aperture_cos: T, radius: T) -> Self {
+        Pie {
+            aperture_sin,
+            aperture_cos,
+            radius,
+        }
+    }
+}
+
+impl<T, V> SDF<T, V> for Pie<T>
+where
+    T: Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Clamp
+        + Compare
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = V::new(p.x().max(-p.x()), p.y());
+        let (sin, cos) = (self.aperture_sin, self.aperture_cos);
+        let side = V::new(sin, cos);
+        let circle = p.magnitude() - self.radius;
+        let edge = (p - side * p.dot(side).clamp(T::zero(), self.radius)).magnitude();
+        circle.max(with_sign(edge, (cos * p.x()).cmp_lt(sin * p.y())))
+    }
+}
+
+/// The number of Newton iterations used to refine the closest point on an `Ellipse`.
+const ELLIPSE_STEPS: usize = 2;
+
+/// A 2D ellipse centered at origin, with radii along the X and Y axes given by `radii`.
+///
+/// The closest point on the ellipse is found by solving a quartic in closed form, as
+/// described by Inigo Quilez at <https://iquilezles.org/articles/ellipsedist/>.
+#[derive(Clone, Copy, Debug)]
+pub struct Ellipse<V> {
+    pub radii: V,
+}
+
+impl<V> Ellipse<V> {
+    pub fn new(radii: V) -> Self {
+        Ellipse { radii }
+    }
+}
+
+impl<T, V> SDF<T, V> for Ellipse<V>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Cbrt
+        + Acos
+        + SinCos
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let (zero, one, two, three) = (T::zero(), T::one(), T::from(2.0), T::from(3.0));
+        let p = p.abs();
+        // Work in the octant where `p.x <= p.y`, swapping the axes if needed.
+        let swap = p.x().cmp_gt(p.y());
+        let (px, py) = (T::select(swap, p.y(), p.x()), T::select(swap, p.x(), p.y()));
+        let (a, b) = (self.radii.x(), self.radii.y());
+        let (a, b) = (T::select(swap, b, a), T::select(swap, a, b));
+
+        // The cosine of the angle of the closest point is a root of a quartic, which is
+        // solved through its resolvent cubic.
+        let l = b * b - a * a;
+        let (m, n) = (a * px / l, b * py / l);
+        let (m2, n2) = (m * m, n * n);
+        let c = (m2 + n2 - one) / three;
+        let c3 = c * c * c;
+        let q = c3 + m2 * n2 * two;
+        let d = c3 + m2 * n2;
+        let g = m + m * n2;
+
+        // With three real roots, use the trigonometric solution.
+        let angle = (q / c3).clamp(-one, one).acos() / three;
+        let (sin, cos) = angle.sin_cos();
+        let t = sin * T::from(3f32.sqrt());
+        let rx = (m2 - c * (cos + t + two)).max(zero).sqrt();
+        let ry = (m2 - c * (cos - t + two)).max(zero).sqrt();
+        let g_abs = g.max(-g);
+        let three_roots =
+            (ry + with_sign(rx, l.cmp_lt(zero)) + g_abs / (rx * ry) - m) * T::point_five();
+
+        // With one, use Cardano's formula.
+        let h = two * m * n * d.max(zero).sqrt();
+        let (s, u) = ((q + h).cbrt(), (q - h).cbrt());
+        let rx = m2 * two - s - u - c * T::from(4.0);
+        let ry = (s - u) * T::from(3f32.sqrt());
+        let rm = (rx * rx + ry * ry).sqrt();
+        let one_root = (ry / (rm - rx).sqrt() + two * g / rm - m) * T::point_five();
+
+        let mut cos = T::select(d.cmp_lt(zero), three_roots, one_root).clamp(zero, one);
+        let mut sin = (one - cos * cos).sqrt();
+
+        // The closed form loses precision to cancellation close to the ellipse, so polish
+        // the angle with Newton's method, rotating the point by the step directly.
+        let p = V::new(px, py);
+        for _ in 0..ELLIPSE_STEPS {
+            let u = V::new(a * cos, b * sin);
+            let v = V::new(-a * sin, b * cos);
+            let d = p - u;
+            let step = d.dot(v) / (d.dot(u) + v.dot(v));
+            let (c, s) = ((cos - step * sin).max(zero), (sin + step * cos).max(zero));
+            let length = (c * c + s * s).sqrt();
+            cos = c / length;
+            sin = s / length;
+        }
+        let dist = (p - V::new(a * cos, b * sin)).magnitude();
+
+        // For a circle, the quartic degenerates.
+        let circle = l.max(-l).cmp_lt(a * a * T::from(1e-6));
+        let dist = T::select(circle, p.magnitude() - a, dist);
+        let (x, y) = (px / a, py / b);
+        with_sign(dist.max(-dist), (x * x + y * y).cmp_lt(one))
+    }
+}
+
+/// The largest real root of `x^3 - 3 p x - 2 q`, which gives the closest point on a
+/// parabola.
+#[inline]
+fn parabola_root<T>(p: T, q: T) -> T
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Sqrt
+        + Cbrt
+        + SinCos
+        + Atan2
+        + Compare
+        + From<f32>
+        + Copy,
+{
+    let h = q * q - p * p * p;
+    let r = h.max(-h).sqrt();
+    // With one real root, use Cardano's formula, and otherwise the trigonometric solution.
+    let one_root = (q + r).cbrt() + (q - r).cbrt();
+    let (_, cos) = (r.atan2(q) / T::from(3.0)).sin_cos();
+    let three_roots = (cos + cos) * p.max(T::zero()).sqrt();
+    T::select(h.cmp_gt(T::zero()), one_root, three_roots)
+}
+
+/// The 2D region above the parabola `y = coefficient * x^2`, which must be positive.
+#[derive(Clone, Copy, Debug)]
+pub struct Parabola<T> {
+    pub coefficient: T,
+}
+
+impl<T> Parabola<T> {
+    pub fn new(coefficient: T) -> Self {
+        Parabola { coefficient }
+    }
+}
+
+impl<T, V> SDF<T, V> for Parabola<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + PointFive
+        + MaxMin
+        + Sqrt
+        + Cbrt
+        + SinCos
+        + Atan2
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = V::new(p.x().max(-p.x()), p.y());
+        let k = self.coefficient;
+        let ik = T::one() / k;
+        let x = parabola_root(
+            ik * (p.y() - T::point_five() * ik) / T::from(3.0),
+            T::point_five() * T::point_five() * ik * ik * p.x(),
+        );
+        let dist = (p - V::new(x, k * x * x)).magnitude();
+        with_sign(dist, p.x().cmp_lt(x))
+    }
+}
+
+/// The 2D region between the X axis and a parabolic arch over it, which is `height` tall
+/// and meets the X axis at `width` to either side of the origin.
+#[derive(Clone, Copy, Debug)]
+pub struct ParabolaSegment<T> {
+    pub width: T,
+    pub height: T,
+}
+
+impl<T> ParabolaSegment<T> {
+    pub fn new(width: T, height: T) -> Self {
+        ParabolaSegment { width, height }
+    }
+}
+
+impl<T, V> SDF<T, V> for ParabolaSegment<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + PointFive
+        + MaxMin
+        + Sqrt
+        + Cbrt
+        + SinCos
+        + Atan2
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = V::new(p.x().max(-p.x()), p.y());
+        let (w, h) = (self.width, self.height);
+        // The arch is `y = h - x^2 / ik`.
+        let ik = w * w / h;
+        let x = parabola_root(
+            ik * (h - p.y() - T::point_five() * ik) / T::from(3.0),
+            T::point_five() * T::point_five() * ik * ik * p.x(),
+        )
+        .min(w);
+        let arch = (p - V::new(x, h - x * x / ik)).magnitude();
+        let base = V::new((p.x() - w).max(T::zero()), p.y()).magnitude();
+        let below_arch = (ik * (p.y() - h) + p.x() * p.x()).cmp_lt(T::zero());
+        with_sign(arch.min(base), below_arch & p.y().cmp_gt(T::zero()))
+    }
+}
+
+/// A 2D vesica, or lens, centered at origin: the intersection of two circles, with its tips
+/// at `width` along the X axis and its sides at `height` along the Y axis. `height` must be
+/// less than `width`.
+#[derive(Clone, Copy, Debug)]
+pub struct Vesica<T> {
+    pub width: T,
+    pub height: T,
+}
+
+impl<T> Vesica<T> {
+    pub fn new(width: T, height: T) -> Self {
+        Vesica { width, height }
+    }
+}
+
+impl<T, V> SDF<T, V> for Vesica<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Zero
+        + PointFive
+        + Compare
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = p.abs();
+        let (w, h) = (self.width, self.height);
+        // The upper side is a circle of radius `d + h` centered at `(0, -d)`.
+        let d = (w * w - h * h) * T::point_five() / h;
+        let past_tip = (w * p.y()).cmp_lt(d * (p.x() - w));
+        let tip = (p - V::new(w, T::zero())).magnitude();
+        let side = (p + V::new(T::zero(), d)).magnitude() - (d + h);
+        T::select(past_tip, tip, side)
+    }
+}
+
+/// A 2D egg shape pointing up the Y axis. The bottom half is a circle of radius `radius`
+/// centered at origin, and the top is pointed, with its tip rounded by `tip_radius`.
+#[derive(Clone, Copy, Debug)]
+pub struct Egg<T> {
+    pub radius: T,
+    pub tip_radius: T,
+}
+
+impl<T> Egg<T> {
+    pub fn new(radius: T, tip_radius: T) -> Self {
+        Egg { radius, tip_radius }
+    }
+}
+
+impl<T, V> SDF<T, V> for Egg<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + MaxMin
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec2<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        let p = V::new(p.x().max(-p.x()), p.y());
+        let k = T::from(3f32.sqrt());
+        let r = self.radius - self.tip_radius;
+        // The sides of the top are arcs of radius `2 r` centered at `(-r, 0)` and `(r, 0)`,
+        // which meet at the tip.
+        let bottom = p.magnitude() - r;
+        let tip = (p - V::new(T::zero(), k * r)).magnitude();
+        let side = (p + V::new(r, T::zero())).magnitude() - (r + r);
+        let top = T::select((k * (p.x() + r)).cmp_lt(p.y()), tip, side);
+        T::select(p.y().cmp_lt(T::zero()), bottom, top) - self.tip_radius
+    }
+}
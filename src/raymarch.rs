@@ -0,0 +1,292 @@
+//! A sphere tracing ray marcher which can be used with any 3D `SDF`.
+//!
+//! Sphere tracing steps along a ray by the distance to the closest surface at each
+//! point, which is always safe to do with a proper distance field. See
+//! [this article](http://iquilezles.org/www/articles/raymarchingdf/raymarchingdf.htm)
+//! for more.
+//!
+//! Over-relaxed sphere tracing, as described by
+//! [Keinert et al.](https://erleuchtet.org/~cupe/permanent/enhanced_sphere_tracing.pdf),
+//! is also available as a `MarchStrategy`.
+use crate::mathtypes::*;
+use crate::util::*;
+use crate::SDF;
+use std::ops::*;
+
+#[cfg(feature = "ultraviolet")]
+use ultraviolet::{f32x4, f32x8};
+
+/// How the `epsilon` of a `Raymarcher` is used to decide that a ray has hit a surface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Epsilon {
+    /// A ray hits when the distance to the surface falls below `epsilon`.
+    Absolute,
+    /// A ray hits when the distance to the surface falls below `epsilon` times the distance
+    /// the ray has traveled, so that the threshold grows along with the footprint of a pixel.
+    Relative,
+}
+
+/// The stepping strategy used by a `Raymarcher`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarchStrategy<T> {
+    /// Plain sphere tracing, stepping exactly the distance to the closest surface.
+    Basic,
+    /// Over-relaxed sphere tracing, stepping `relaxation` times the distance to the closest
+    /// surface. Whenever the unbounding spheres of two consecutive steps stop overlapping, the
+    /// step is undone and the ray falls back to basic stepping. `relaxation` should be in
+    /// the range `[1, 2)`; 1.6 is a good default value to try.
+    OverRelaxed { relaxation: T },
+}
+
+/// The reason a ray stopped marching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarchOutcome {
+    /// The ray got within the hit threshold of a surface.
+    Hit,
+    /// The ray ran out of steps before hitting anything or leaving the scene.
+    MaxSteps,
+    /// The ray traveled further than the maximum distance without hitting anything.
+    MaxDistance,
+}
+
+/// The result of marching a ray through an SDF.
+///
+/// For SIMD scalar types, every field holds one result per lane and `hit` and `max_distance`
+/// are per-lane masks. Lanes that are set in neither mask ran out of steps.
+#[derive(Clone, Copy, Debug)]
+pub struct MarchHit<T: Compare, V> {
+    /// The distance traveled along the ray.
+    pub distance: T,
+    /// The point the ray stopped at.
+    pub point: V,
+    /// The number of steps taken. For SIMD scalar types, this is the number of steps
+    /// taken until the last lane terminated.
+    pub steps: usize,
+    /// Set for lanes which hit a surface.
+    pub hit: T::Mask,
+    /// Set for lanes which traveled further than the maximum distance.
+    pub max_distance: T::Mask,
+    /// The normal at `point`, if the march was done with `Raymarcher::march_with_normals`.
+    pub normal: Option<V>,
+}
+
+impl<T: Compare, V> MarchHit<T, V> {
+    /// Lanes which ran out of steps before hitting a surface or exceeding the maximum distance.
+    pub fn max_steps(&self) -> T::Mask {
+        !(self.hit | self.max_distance)
+    }
+}
+
+impl<T: Compare<Mask = bool>, V> MarchHit<T, V> {
+    /// The reason this ray stopped marching. Only available for non-SIMD scalar types.
+    pub fn outcome(&self) -> MarchOutcome {
+        if self.hit {
+            MarchOutcome::Hit
+        } else if self.max_distance {
+            MarchOutcome::MaxDistance
+        } else {
+            MarchOutcome::MaxSteps
+        }
+    }
+}
+
+/// A configurable sphere tracer.
+#[derive(Clone, Copy, Debug)]
+pub struct Raymarcher<T> {
+    /// The maximum number of steps to take along a ray.
+    pub max_steps: usize,
+    /// The distance along the ray after which it is considered a miss.
+    pub max_distance: T,
+    /// The hit threshold; see `epsilon_mode`.
+    pub epsilon: T,
+    /// How `epsilon` is used to decide whether the ray hit a surface.
+    pub epsilon_mode: Epsilon,
+    /// How the ray steps through the SDF. `Raymarcher::new` uses `MarchStrategy::Basic`.
+    pub strategy: MarchStrategy<T>,
+}
+
+impl<T> Raymarcher<T> {
+    pub fn new(max_steps: usize, max_distance: T, epsilon: T, epsilon_mode: Epsilon) -> Self {
+        Raymarcher {
+            max_steps,
+            max_distance,
+            epsilon,
+            epsilon_mode,
+            strategy: MarchStrategy::Basic,
+        }
+    }
+}
+
+impl<T> Raymarcher<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Compare
+        + Copy,
+{
+    /// March a ray starting at `origin` in direction `dir` through `sdf`, using
+    /// this marcher's `strategy`.
+    ///
+    /// `dir` should be normalized. For SIMD scalar types, each lane stops marching as soon as it
+    /// hits a surface or exceeds the maximum distance, and the march ends once every lane has
+    /// stopped or `max_steps` is reached.
+    pub fn march<V, S>(&self, sdf: S, origin: V, dir: V) -> MarchHit<T, V>
+    where
+        V: Vec3<T>,
+        S: SDF<T, V>,
+    {
+        match self.strategy {
+            MarchStrategy::Basic => self.march_basic(sdf, origin, dir),
+            MarchStrategy::OverRelaxed { relaxation } => {
+                self.march_over_relaxed(sdf, origin, dir, relaxation)
+            }
+        }
+    }
+
+    /// March a ray with both `MarchStrategy::Basic` and this marcher's `strategy`, and
+    /// return how many fewer steps this marcher's `strategy` needed. This is negative if
+    /// it needed more steps than basic sphere tracing.
+    pub fn steps_saved<V, S>(&self, sdf: S, origin: V, dir: V) -> isize
+    where
+        V: Vec3<T>,
+        S: SDF<T, V>,
+    {
+        let basic = self.march_basic(sdf, origin, dir);
+        let this = self.march(sdf, origin, dir);
+        basic.steps as isize - this.steps as isize
+    }
+
+    #[inline]
+    fn threshold(&self, t: T) -> T {
+        match self.epsilon_mode {
+            Epsilon::Absolute => self.epsilon,
+            Epsilon::Relative => self.epsilon * t,
+        }
+    }
+
+    fn march_basic<V, S>(&self, sdf: S, origin: V, dir: V) -> MarchHit<T, V>
+    where
+        V: Vec3<T>,
+        S: SDF<T, V>,
+    {
+        let mut t = T::zero();
+        let mut active = T::Mask::splat(true);
+        let mut hit = T::Mask::splat(false);
+        let mut max_distance = T::Mask::splat(false);
+        let mut steps = 0;
+
+        while steps < self.max_steps && active.any() {
+            steps += 1;
+            let d = sdf.dist(origin + dir * t);
+
+            let new_hit = active & d.cmp_lt(self.threshold(t));
+            hit = hit | new_hit;
+            active = active & !new_hit;
+
+            t = T::select(active, t + d, t);
+
+            let new_max_distance = active & t.cmp_gt(self.max_distance);
+            max_distance = max_distance | new_max_distance;
+            active = active & !new_max_distance;
+        }
+
+        MarchHit {
+            distance: t,
+            point: origin + dir * t,
+            steps,
+            hit,
+            max_distance,
+            normal: None,
+        }
+    }
+
+    fn march_over_relaxed<V, S>(&self, sdf: S, origin: V, dir: V, relaxation: T) -> MarchHit<T, V>
+    where
+        V: Vec3<T>,
+        S: SDF<T, V>,
+    {
+        let mut t = T::zero();
+        let mut omega = relaxation;
+        let mut step = T::zero();
+        let mut prev_radius = T::zero();
+        let mut active = T::Mask::splat(true);
+        let mut hit = T::Mask::splat(false);
+        let mut max_distance = T::Mask::splat(false);
+        let mut steps = 0;
+
+        while steps < self.max_steps && active.any() {
+            steps += 1;
+            let d = sdf.dist(origin + dir * t);
+            let radius = d.max(-d);
+
+            // If the unbounding spheres of this and the previous point don't overlap, the
+            // relaxed step may have skipped over a surface, so step back to where a basic step
+            // would have ended up and stop relaxing.
+            let fail = omega.cmp_gt(T::one()) & (radius + prev_radius).cmp_lt(step);
+            step = T::select(fail, prev_radius - step, d * omega);
+            omega = T::select(fail, T::one(), omega);
+            prev_radius = radius;
+
+            let new_hit = active & !fail & d.cmp_lt(self.threshold(t));
+            hit = hit | new_hit;
+            active = active & !new_hit;
+
+            t = T::select(active, t + step, t);
+
+            let new_max_distance = active & t.cmp_gt(self.max_distance);
+            max_distance = max_distance | new_max_distance;
+            active = active & !new_max_distance;
+        }
+
+        MarchHit {
+            distance: t,
+            point: origin + dir * t,
+            steps,
+            hit,
+            max_distance,
+            normal: None,
+        }
+    }
+
+    /// March a ray through the SDF owned by `sdf` like `march` does, and then estimate the
+    /// normal at the point where the ray stopped.
+    pub fn march_with_normals<V, S, E>(
+        &self,
+        sdf: &EstimateNormal<T, V, S, E>,
+        origin: V,
+        dir: V,
+    ) -> MarchHit<T, V>
+    where
+        V: Vec3<T>,
+        S: SDF<T, V>,
+        E: NormalEstimator<T, V>,
+    {
+        let mut hit = self.march(sdf.sdf, origin, dir);
+        hit.normal = Some(sdf.normal_at(hit.point));
+        hit
+    }
+}
+
+macro_rules! impl_default_raymarcher {
+    ($($scalar_t:ty),+) => {
+        $(impl Default for Raymarcher<$scalar_t> {
+            fn default() -> Self {
+                Self::new(
+                    128,
+                    <$scalar_t>::from(100.0),
+                    <$scalar_t>::from(0.001),
+                    Epsilon::Absolute,
+                )
+            }
+        })+
+    }
+}
+
+impl_default_raymarcher!(f32, f64);
+#[cfg(feature = "ultraviolet")]
+impl_default_raymarcher!(f32x4, f32x8);
@@ -0,0 +1,322 @@
+//! Sweeping a 2D profile SDF along a 3D path, for shapes like pipes, moldings and rails.
+//!
+//! The path is flattened into a polyline, along which a rotation-minimizing frame is carried
+//! by parallel transport, so that the profile does not twist around the path. To evaluate
+//! the distance to the sweep, the closest point on the path is found and the profile is
+//! evaluated in the frame there. At corners of the polyline, the frame rotates around the
+//! corner so that the outside of the corner is filled in. Since curves are only approximated
+//! by the polyline, distances are reduced by how far they stray from its closest segment.
+use crate::mathtypes::{Real, Vec2, Vec3};
+use crate::primitives::segment_dist2;
+use crate::SDF;
+
+/// How many times a curved `PathPiece` can be halved while flattening it, however small the
+/// tolerance.
+const MAX_CURVE_DEPTH: usize = 16;
+
+/// A piece of a `SweepPath`. Each piece should start where the previous one ends.
+#[derive(Clone, Copy, Debug)]
+pub enum PathPiece<V> {
+    /// A straight line from the first point to the second.
+    Line(V, V),
+    /// A quadratic Bézier curve from the first point to the last, with the middle point as
+    /// its control point.
+    QuadraticBezier(V, V, V),
+    /// A cubic Bézier curve from the first point to the last, with the middle two points as
+    /// its control points.
+    CubicBezier(V, V, V, V),
+}
+
+impl<V> PathPiece<V> {
+    /// Appends the points of this piece after its start to `points`, flattening curves into
+    /// line segments that they stray no further than `tolerance` from. Each point is paired
+    /// with a bound on how far the curve strays from the segment ending at it.
+    fn flatten<T>(&self, tolerance: T, points: &mut Vec<(V, T)>)
+    where
+        T: Real,
+        V: Vec3<T>,
+    {
+        match *self {
+            PathPiece::Line(_, b) => points.push((b, T::zero())),
+            PathPiece::QuadraticBezier(a, b, c) => {
+                // Elevated to a cubic with the same shape.
+                let two_thirds = T::from_f64(2.0 / 3.0);
+                let (b, c, d) = (a + (b - a) * two_thirds, c + (b - c) * two_thirds, c);
+                flatten_cubic([a, b, c, d], tolerance, 0, points);
+            }
+            PathPiece::CubicBezier(a, b, c, d) => flatten_cubic([a, b, c, d], tolerance, 0, points),
+        }
+    }
+
+    fn start(&self) -> V
+    where
+        V: Copy,
+    {
+        match *self {
+            PathPiece::Line(a, _)
+            | PathPiece::QuadraticBezier(a, _, _)
+            | PathPiece::CubicBezier(a, _, _, _) => a,
+        }
+    }
+}
+
+/// Appends the end of the cubic Bézier curve with control points `[a, b, c, d]` to `points`,
+/// halving it until each part strays no further than `tolerance` from its chord.
+fn flatten_cubic<T, V>([a, b, c, d]: [V; 4], tolerance: T, depth: usize, points: &mut Vec<(V, T)>)
+where
+    T: Real,
+    V: Vec3<T>,
+{
+    // The curve lies within the hull of its control points, so the furthest of them from the
+    // chord bounds how far the curve strays from it.
+    let chord = d - a;
+    let chord_dist2 = |v: V| {
+        if chord.dot(chord) > T::zero() {
+            segment_dist2(v - a, chord)
+        } else {
+            (v - a).dot(v - a)
+        }
+    };
+    let deviation = chord_dist2(b).max(chord_dist2(c)).sqrt();
+    if deviation <= tolerance || depth == MAX_CURVE_DEPTH {
+        points.push((d, deviation));
+        return;
+    }
+    let half = T::from_f64(0.5);
+    let (ab, bc, cd) = ((a + b) * half, (b + c) * half, (c + d) * half);
+    let (abc, bcd) = ((ab + bc) * half, (bc + cd) * half);
+    let middle = (abc + bcd) * half;
+    flatten_cubic([a, ab, abc, middle], tolerance, depth + 1, points);
+    flatten_cubic([middle, bcd, cd, d], tolerance, depth + 1, points);
+}
+
+/// The direction of a segment of the path, and the directions of the X and Y axes of the
+/// profile along it.
+#[derive(Clone, Copy, Debug)]
+struct Frame<V> {
+    tangent: V,
+    x: V,
+    y: V,
+}
+
+/// An open path to sweep a profile along with `Sweep`.
+///
+/// The frame the profile is evaluated in starts with its Y axis pointing as close to `up`
+/// as possible, and its X axis to the right when looking along the path. From there, it is
+/// rotation-minimizing: it only turns as much as needed to follow the path.
+#[derive(Clone, Debug)]
+pub struct SweepPath<T, V> {
+    points: Vec<V>,
+    frames: Vec<Frame<V>>,
+    deviations: Vec<T>,
+}
+
+impl<T: Copy, V> SweepPath<T, V> {
+    /// The points of the polyline the path is flattened into.
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    /// For each segment of the polyline, a bound on how far the part of the path it stands
+    /// in for strays from it. Distances to a `Sweep` are reduced by the value of the closest
+    /// segment so that they remain a bound.
+    pub fn deviations(&self) -> &[T] {
+        &self.deviations
+    }
+
+    /// Creates a path from `pieces`, with the profile's Y axis starting out towards `up`.
+    /// Curved pieces are flattened into as many segments as it takes for them to stray no
+    /// further than `tolerance` from the polyline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path has no length, such as when `pieces` is empty.
+    pub fn new(pieces: &[PathPiece<V>], up: V, tolerance: T) -> Self
+    where
+        T: Real,
+        V: Vec3<T>,
+    {
+        let mut flattened = Vec::new();
+        if let Some(first) = pieces.first() {
+            flattened.push((first.start(), T::zero()));
+        }
+        for piece in pieces {
+            piece.flatten(tolerance, &mut flattened);
+        }
+        // Degenerate segments have no direction, so drop them, keeping their deviation with
+        // the segment before them.
+        let epsilon = T::from_f64(1e-12);
+        let mut points: Vec<V> = Vec::with_capacity(flattened.len());
+        let mut deviations: Vec<T> = Vec::with_capacity(flattened.len());
+        for (p, deviation) in flattened {
+            match points.last() {
+                Some(&last) if (p - last).dot(p - last) <= epsilon => {
+                    if let Some(previous) = deviations.last_mut() {
+                        *previous = previous.max(deviation);
+                    }
+                }
+                Some(_) => {
+                    points.push(p);
+                    deviations.push(deviation);
+                }
+                None => points.push(p),
+            }
+        }
+        assert!(
+            points.len() > 1,
+            "SweepPath must have at least one segment of nonzero length"
+        );
+
+        let mut frames: Vec<Frame<V>> = Vec::with_capacity(points.len() - 1);
+        for pair in points.windows(2) {
+            let tangent = (pair[1] - pair[0]).normalized();
+            let y = match frames.last() {
+                Some(previous) => transport(previous, tangent),
+                None => {
+                    let mut up = up - tangent * up.dot(tangent);
+                    if up.dot(up) < epsilon {
+                        // `up` is along the path, so pick any other direction.
+                        let other = if tangent.x().max(-tangent.x()) < T::from_f64(0.9) {
+                            V::new(T::one(), T::zero(), T::zero())
+                        } else {
+                            V::new(T::zero(), T::one(), T::zero())
+                        };
+                        up = other - tangent * other.dot(tangent);
+                    }
+                    up
+                }
+            };
+            // Keep the frame orthonormal as rounding errors accumulate.
+            let y = (y - tangent * y.dot(tangent)).normalized();
+            frames.push(Frame {
+                tangent,
+                x: y.cross(tangent),
+                y,
+            });
+        }
+        SweepPath {
+            points,
+            frames,
+            deviations,
+        }
+    }
+}
+
+/// The Y axis of `previous` after the smallest rotation that turns its tangent to `tangent`.
+fn transport<T, V>(previous: &Frame<V>, tangent: V) -> V
+where
+    T: Real,
+    V: Vec3<T>,
+{
+    let (y, cos) = (previous.y, previous.tangent.dot(tangent));
+    if cos + T::one() < T::from_f64(1e-9) {
+        // The path turns back on itself, so any axis perpendicular to it will do.
+        return -y;
+    }
+    // Rodrigues' rotation formula, with the axis scaled by the sine of the angle.
+    let k = previous.tangent.cross(tangent);
+    y * cos + k.cross(y) + k * (k.dot(y) / (T::one() + cos))
+}
+
+/// Sweep a 2D SDF along a `SweepPath`. The 2D SDF's origin follows the path, with its axes
+/// following the path's frame, and the ends of the sweep are capped flat.
+///
+/// The result is only correct if each part of the profile stays closer to its own part of
+/// the path than to any other: the profile should be small compared to the radius of
+/// curvature of the path, and to the distance between its parts. When it is, the distance
+/// is a conservative bound for the profile swept along the polyline the path is flattened
+/// into, which is exact for straight paths.
+///
+/// Along curves, the distance is reduced by how far the curve strays from the polyline,
+/// but that only covers the change in position. The profile keeps the frame of each segment
+/// and turns at the corners between them rather than smoothly along the curve, so profiles
+/// that are not round can be rotated slightly from where the curve would put them, and
+/// their distance is not a bound for that difference.
+#[derive(Clone, Copy, Debug)]
+pub struct Sweep<'a, S, T, V> {
+    pub sdf: S,
+    pub path: &'a SweepPath<T, V>,
+}
+
+impl<'a, S, T, V> Sweep<'a, S, T, V> {
+    pub fn new(sdf: S, path: &'a SweepPath<T, V>) -> Self {
+        Sweep { sdf, path }
+    }
+}
+
+impl<T, V, S> SDF<T, V> for Sweep<'_, S, T, V>
+where
+    T: Real,
+    V: Vec3<T>,
+    S: SDF<T, V::Vec2>,
+{
+    fn dist(&self, p: V) -> T {
+        let (points, frames) = (&self.path.points, &self.path.frames);
+        let mut closest = (T::zero(), 0, T::zero());
+        for (i, frame) in frames.iter().enumerate() {
+            let (start, end) = (points[i], points[i + 1]);
+            let t = ((p - start).dot(frame.tangent) / (end - start).magnitude())
+                .clamp(T::zero(), T::one());
+            let d = p - (start + (end - start) * t);
+            let d = d.dot(d);
+            if i == 0 || d < closest.0 {
+                closest = (d, i, t);
+            }
+        }
+        let (_, i, t) = closest;
+        let last = frames.len() - 1;
+
+        // The offset from the closest point, in the profile's frame.
+        let local = if t >= T::one() && i < last || t <= T::zero() && i > 0 {
+            let corner = if t >= T::one() { i } else { i - 1 };
+            around_corner(&frames[corner], &frames[corner + 1], p - points[corner + 1])
+        } else {
+            let v = p - (points[i] + (points[i + 1] - points[i]) * t);
+            V::Vec2::new(v.dot(frames[i].x), v.dot(frames[i].y))
+        };
+        let d = self.sdf.dist(local);
+
+        // On the first and last segments, the distance past the flat caps at the ends.
+        let mut axial = None;
+        if i == 0 {
+            axial = Some((points[0] - p).dot(frames[0].tangent));
+        }
+        if i == last {
+            let past_end = (p - points[last + 1]).dot(frames[last].tangent);
+            axial = Some(axial.map_or(past_end, |a: T| a.max(past_end)));
+        }
+        let d = match axial {
+            Some(axial) => {
+                let (x, y) = (d.max(T::zero()), axial.max(T::zero()));
+                d.max(axial).min(T::zero()) + (x * x + y * y).sqrt()
+            }
+            None => d,
+        };
+        d - self.path.deviations[i]
+    }
+}
+
+/// The offset `v` from the corner between the segments with frames `a` and `b`, in the
+/// profile's frame. Outside the corner, the frame is rotated part of the way from `a` to `b`
+/// so that its tangent is perpendicular to `v`.
+fn around_corner<T, V>(a: &Frame<V>, b: &Frame<V>, v: V) -> V::Vec2
+where
+    T: Real,
+    V: Vec3<T>,
+{
+    let in_frame = |frame: &Frame<V>, v: V| V::Vec2::new(v.dot(frame.x), v.dot(frame.y));
+    let axis = a.tangent.cross(b.tangent);
+    if v.dot(a.tangent) < T::zero() || axis.dot(axis) < T::from_f64(1e-12) {
+        return in_frame(a, v);
+    }
+    if v.dot(b.tangent) > T::zero() {
+        return in_frame(b, v);
+    }
+    // Rotating `v` back around the axis of the corner, by the angle that makes the tangent
+    // perpendicular to it, moves all of its component in the plane of the corner to `-w`.
+    let axis = axis.normalized();
+    let w = axis.cross(a.tangent);
+    let (along, across) = (v.dot(a.tangent), v.dot(w));
+    let rotated = axis * v.dot(axis) - w * (along * along + across * across).sqrt();
+    in_frame(a, rotated)
+}
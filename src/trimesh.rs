@@ -0,0 +1,518 @@
+//! An SDF for arbitrary triangle meshes, so that imported meshes can be combined with the
+//! analytic primitives in this crate.
+//!
+//! The distance to the mesh is found by searching for the closest triangle with a
+//! bounding volume hierarchy (BVH), and the sign is determined with angle-weighted
+//! pseudonormals as described by
+//! [Bærentzen and Aanæs](http://www2.imm.dtu.dk/pubdb/edoc/imm1289.pdf). This requires the mesh
+//! to be closed and consistently wound counter-clockwise when viewed from the outside.
+use crate::mathtypes::{Real, Vec3};
+use crate::meshing::Mesh;
+use crate::SDF;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read};
+
+/// The maximum number of triangles in a leaf of the BVH.
+const LEAF_SIZE: usize = 4;
+
+#[derive(Clone, Debug)]
+enum NodeKind {
+    Leaf { start: usize, end: usize },
+    Branch { left: usize, right: usize },
+}
+
+#[derive(Clone, Debug)]
+struct Node<V> {
+    min: V,
+    max: V,
+    kind: NodeKind,
+}
+
+/// The feature of a triangle that a point is closest to. Edge `i` connects vertex `i`
+/// and vertex `i + 1`.
+#[derive(Clone, Copy, Debug)]
+enum Feature {
+    Vertex(usize),
+    Edge(usize),
+    Face,
+}
+
+/// A closed triangle mesh which can be used as an SDF.
+///
+/// Since the mesh owns its data, it does not implement `SDF` itself; instead, a reference
+/// to it does. This means it can be combined with other SDFs like so:
+///
+/// ```rust
+/// # #[cfg(feature = "ultraviolet")]
+/// # fn main() {
+/// use sdfu::SDF;
+/// use ultraviolet::Vec3;
+///
+/// let tetrahedron = sdfu::trimesh::TriangleMesh::new(
+///     vec![
+///         Vec3::new(1.0, 1.0, 1.0),
+///         Vec3::new(1.0, -1.0, -1.0),
+///         Vec3::new(-1.0, 1.0, -1.0),
+///         Vec3::new(-1.0, -1.0, 1.0),
+///     ],
+///     vec![[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]],
+/// );
+/// let sdf = (&tetrahedron).union_smooth(sdfu::Sphere::new(0.5).translate(Vec3::unit_x()), 0.1);
+/// # }
+/// # #[cfg(not(feature = "ultraviolet"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug)]
+pub struct TriangleMesh<V> {
+    vertices: Vec<V>,
+    triangles: Vec<[u32; 3]>,
+    face_normals: Vec<V>,
+    edge_normals: Vec<[V; 3]>,
+    vertex_normals: Vec<V>,
+    nodes: Vec<Node<V>>,
+}
+
+impl<V> TriangleMesh<V> {
+    /// The vertices of the mesh.
+    pub fn vertices(&self) -> &[V] {
+        &self.vertices
+    }
+
+    /// The triangles of the mesh, as indices into `vertices`. These are reordered from the
+    /// order they were given in when building the BVH.
+    pub fn triangles(&self) -> &[[u32; 3]] {
+        &self.triangles
+    }
+
+    /// Creates a mesh SDF from `vertices` and `triangles` indexing into them.
+    ///
+    /// Triangles must be wound counter-clockwise when viewed from the outside, and must share
+    /// vertices with their neighbors rather than duplicating them, since the sign of the
+    /// distance is determined from the normals of adjacent triangles.
+    pub fn new<T>(vertices: Vec<V>, triangles: Vec<[u32; 3]>) -> Self
+    where
+        T: Real,
+        V: Vec3<T>,
+    {
+        let mut mesh = TriangleMesh {
+            vertices,
+            triangles,
+            face_normals: Vec::new(),
+            edge_normals: Vec::new(),
+            vertex_normals: Vec::new(),
+            nodes: Vec::new(),
+        };
+        let mut order: Vec<usize> = (0..mesh.triangles.len()).collect();
+        if !order.is_empty() {
+            mesh.build_node(&mut order, 0);
+        }
+        mesh.triangles = order.iter().map(|&i| mesh.triangles[i]).collect();
+        mesh.compute_pseudonormals();
+        mesh
+    }
+
+    /// Creates a mesh SDF from a `Mesh`, such as one extracted from another SDF. Quads are
+    /// split into triangles.
+    pub fn from_mesh<T>(mesh: &Mesh<V>) -> Self
+    where
+        T: Real,
+        V: Vec3<T>,
+    {
+        let mut mesh = mesh.clone();
+        mesh.triangulate();
+        Self::new(mesh.vertices, mesh.triangles)
+    }
+
+    /// Loads a mesh SDF from a Wavefront OBJ file. Only vertex positions and faces are read,
+    /// and faces with more than three vertices are split into triangles.
+    pub fn from_obj<T, R>(reader: R) -> io::Result<Self>
+    where
+        T: Real,
+        V: Vec3<T>,
+        R: BufRead,
+    {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let mut coords = [T::zero(); 3];
+                    for c in coords.iter_mut() {
+                        *c = parse_float(tokens.next())?;
+                    }
+                    vertices.push(V::new(coords[0], coords[1], coords[2]));
+                }
+                Some("f") => {
+                    let face = tokens
+                        .map(|token| {
+                            let index = token.split('/').next().unwrap_or("");
+                            let index: i64 = index.parse().map_err(|_| invalid_data(token))?;
+                            // OBJ indices start at one, and negative ones count from the end.
+                            let resolved = if index < 0 {
+                                vertices.len() as i64 + index
+                            } else {
+                                index - 1
+                            };
+                            if resolved < 0 || resolved >= vertices.len() as i64 {
+                                return Err(invalid_data(token));
+                            }
+                            Ok(resolved as u32)
+                        })
+                        .collect::<io::Result<Vec<u32>>>()?;
+                    for i in 1..face.len().saturating_sub(1) {
+                        triangles.push([face[0], face[i], face[i + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(Self::new(vertices, triangles))
+    }
+
+    /// Loads a mesh SDF from an ASCII or binary STL file. Since STL files don't share
+    /// vertices between triangles, vertices at identical positions are merged.
+    pub fn from_stl<T, R>(mut reader: R) -> io::Result<Self>
+    where
+        T: Real,
+        V: Vec3<T>,
+        R: Read,
+    {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut positions = Vec::new();
+        let is_binary = data.len() >= 84 && {
+            let count = u32::from_le_bytes([data[80], data[81], data[82], data[83]]) as usize;
+            data.len() == 84 + 50 * count
+        };
+        if is_binary {
+            for triangle in data[84..].chunks_exact(50) {
+                // Skip the facet normal and read the three vertices.
+                for vertex in triangle[12..48].chunks_exact(12) {
+                    let mut coords = [0.0; 3];
+                    for (c, bytes) in coords.iter_mut().zip(vertex.chunks_exact(4)) {
+                        *c = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    }
+                    positions.push(coords);
+                }
+            }
+        } else {
+            let text = std::str::from_utf8(&data).map_err(|_| invalid_data("non-UTF-8 STL"))?;
+            for line in text.lines() {
+                let mut tokens = line.split_whitespace();
+                if tokens.next() == Some("vertex") {
+                    let mut coords = [0.0; 3];
+                    for c in coords.iter_mut() {
+                        let token = tokens.next().unwrap_or("");
+                        *c = token.parse().map_err(|_| invalid_data(token))?;
+                    }
+                    positions.push(coords);
+                }
+            }
+            if positions.len() % 3 != 0 {
+                return Err(invalid_data("incomplete STL facet"));
+            }
+        }
+
+        let mut vertices = Vec::new();
+        let mut indices = HashMap::new();
+        let mut triangles = Vec::with_capacity(positions.len() / 3);
+        for triangle in positions.chunks_exact(3) {
+            let mut tri = [0; 3];
+            for (index, &[x, y, z]) in tri.iter_mut().zip(triangle.iter()) {
+                let key = [x.to_bits(), y.to_bits(), z.to_bits()];
+                *index = *indices.entry(key).or_insert_with(|| {
+                    vertices.push(V::new(
+                        T::from_f64(x as f64),
+                        T::from_f64(y as f64),
+                        T::from_f64(z as f64),
+                    ));
+                    (vertices.len() - 1) as u32
+                });
+            }
+            triangles.push(tri);
+        }
+        Ok(Self::new(vertices, triangles))
+    }
+
+    #[inline]
+    fn triangle<T>(&self, i: usize) -> [V; 3]
+    where
+        V: Vec3<T>,
+    {
+        let [a, b, c] = self.triangles[i];
+        [
+            self.vertices[a as usize],
+            self.vertices[b as usize],
+            self.vertices[c as usize],
+        ]
+    }
+
+    /// Builds the BVH node containing the triangles in `order` and its children, reordering
+    /// `order` so that the triangles of each leaf are contiguous. Returns the node's index.
+    fn build_node<T>(&mut self, order: &mut [usize], start: usize) -> usize
+    where
+        T: Real,
+        V: Vec3<T>,
+    {
+        let first = self.triangle(order[0])[0];
+        let (mut min, mut max) = (first, first);
+        let (mut centroid_min, mut centroid_max) = (first, first);
+        for (n, &i) in order.iter().enumerate() {
+            let [a, b, c] = self.triangle(i);
+            min = min.min(a).min(b).min(c);
+            max = max.max(a).max(b).max(c);
+            let centroid = centroid(a, b, c);
+            if n == 0 {
+                centroid_min = centroid;
+                centroid_max = centroid;
+            }
+            centroid_min = centroid_min.min(centroid);
+            centroid_max = centroid_max.max(centroid);
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            min,
+            max,
+            kind: NodeKind::Leaf {
+                start,
+                end: start + order.len(),
+            },
+        });
+        if order.len() <= LEAF_SIZE {
+            return index;
+        }
+
+        // Split the triangles in half along the axis their centroids are most spread out on.
+        let extent = centroid_max - centroid_min;
+        let axis = if extent.x() >= extent.y() && extent.x() >= extent.z() {
+            0
+        } else if extent.y() >= extent.z() {
+            1
+        } else {
+            2
+        };
+        let key = |i: usize| {
+            let [a, b, c] = self.triangle(i);
+            let centroid = centroid(a, b, c);
+            match axis {
+                0 => centroid.x(),
+                1 => centroid.y(),
+                _ => centroid.z(),
+            }
+        };
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by(mid, |&a, &b| {
+            key(a)
+                .partial_cmp(&key(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let (left_order, right_order) = order.split_at_mut(mid);
+        let left = self.build_node(left_order, start);
+        let right = self.build_node(right_order, start + mid);
+        self.nodes[index].kind = NodeKind::Branch { left, right };
+        index
+    }
+
+    fn compute_pseudonormals<T>(&mut self)
+    where
+        T: Real,
+        V: Vec3<T>,
+    {
+        let normalized = |v: V| {
+            if v.magnitude() > T::zero() {
+                v.normalized()
+            } else {
+                v
+            }
+        };
+
+        let mut vertex_normals = vec![V::zero(); self.vertices.len()];
+        let mut edge_sums: HashMap<(u32, u32), V> = HashMap::new();
+        self.face_normals = Vec::with_capacity(self.triangles.len());
+        for i in 0..self.triangles.len() {
+            let indices = self.triangles[i];
+            let [a, b, c] = self.triangle(i);
+            let normal = normalized((b - a).cross(c - a));
+            self.face_normals.push(normal);
+
+            let corners = [a, b, c];
+            for k in 0..3 {
+                let (v, next, prev) = (corners[k], corners[(k + 1) % 3], corners[(k + 2) % 3]);
+                let (e1, e2) = (next - v, prev - v);
+                let angle = e1.cross(e2).magnitude().to_f64().atan2(e1.dot(e2).to_f64());
+                let vertex = indices[k] as usize;
+                vertex_normals[vertex] = vertex_normals[vertex] + normal * T::from_f64(angle);
+
+                let edge = edge_key(indices[k], indices[(k + 1) % 3]);
+                let sum = edge_sums.entry(edge).or_insert_with(V::zero);
+                *sum = *sum + normal;
+            }
+        }
+
+        self.vertex_normals = vertex_normals.into_iter().map(normalized).collect();
+        self.edge_normals = self
+            .triangles
+            .iter()
+            .map(|t| {
+                let edge = |k: usize| normalized(edge_sums[&edge_key(t[k], t[(k + 1) % 3])]);
+                [edge(0), edge(1), edge(2)]
+            })
+            .collect();
+    }
+
+    /// Finds the closest point on the mesh to `p`, returning its squared distance, position,
+    /// and the pseudonormal of the closest feature.
+    fn closest<T>(&self, p: V) -> Option<(T, V, V)>
+    where
+        T: Real,
+        V: Vec3<T>,
+    {
+        let mut best: Option<(T, V, V)> = None;
+        let mut stack = Vec::with_capacity(64);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if let Some((best_dist, _, _)) = best {
+                if box_distance_squared(p, node.min, node.max) > best_dist {
+                    continue;
+                }
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for i in start..end {
+                        let [a, b, c] = self.triangle(i);
+                        let (q, feature) = closest_on_triangle(p, a, b, c);
+                        let dist = (p - q).dot(p - q);
+                        if best.map_or(true, |(best_dist, _, _)| dist < best_dist) {
+                            let normal = match feature {
+                                Feature::Vertex(k) => {
+                                    self.vertex_normals[self.triangles[i][k] as usize]
+                                }
+                                Feature::Edge(k) => self.edge_normals[i][k],
+                                Feature::Face => self.face_normals[i],
+                            };
+                            best = Some((dist, q, normal));
+                        }
+                    }
+                }
+                NodeKind::Branch { left, right } => {
+                    // Visit the closer child first so that the other one is more likely to
+                    // be culled.
+                    let dl = box_distance_squared(p, self.nodes[left].min, self.nodes[left].max);
+                    let dr = box_distance_squared(p, self.nodes[right].min, self.nodes[right].max);
+                    if dl < dr {
+                        stack.push(right);
+                        stack.push(left);
+                    } else {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl<T, V> SDF<T, V> for &TriangleMesh<V>
+where
+    T: Real,
+    V: Vec3<T>,
+{
+    #[inline]
+    fn dist(&self, p: V) -> T {
+        match self.closest(p) {
+            Some((dist, q, normal)) => {
+                let dist = dist.sqrt();
+                if (p - q).dot(normal) < T::zero() {
+                    -dist
+                } else {
+                    dist
+                }
+            }
+            None => T::from_f64(f64::INFINITY),
+        }
+    }
+}
+
+#[inline]
+fn edge_key(a: u32, b: u32) -> (u32, u32) {
+    (a.min(b), a.max(b))
+}
+
+#[inline]
+fn centroid<T: Real, V: Vec3<T>>(a: V, b: V, c: V) -> V {
+    (a + b + c) / T::from_f64(3.0)
+}
+
+fn invalid_data(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid mesh data: {:?}", what),
+    )
+}
+
+fn parse_float<T: Real>(token: Option<&str>) -> io::Result<T> {
+    let token = token.unwrap_or("");
+    token
+        .parse::<f64>()
+        .map(T::from_f64)
+        .map_err(|_| invalid_data(token))
+}
+
+#[inline]
+fn box_distance_squared<T: Real, V: Vec3<T>>(p: V, min: V, max: V) -> T {
+    let d = (min - p).max(p - max).max(V::zero());
+    d.dot(d)
+}
+
+/// Finds the closest point to `p` on the triangle `abc` and the feature it lies on.
+/// See Real-Time Collision Detection by Christer Ericson, section 5.1.5.
+fn closest_on_triangle<T: Real, V: Vec3<T>>(p: V, a: V, b: V, c: V) -> (V, Feature) {
+    let zero = T::zero();
+    let (ab, ac, ap) = (b - a, c - a, p - a);
+    let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+    if d1 <= zero && d2 <= zero {
+        return (a, Feature::Vertex(0));
+    }
+
+    let bp = p - b;
+    let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+    if d3 >= zero && d4 <= d3 {
+        return (b, Feature::Vertex(1));
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= zero && d1 >= zero && d3 <= zero {
+        return (a + ab * (d1 / (d1 - d3)), Feature::Edge(0));
+    }
+
+    let cp = p - c;
+    let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+    if d6 >= zero && d5 <= d6 {
+        return (c, Feature::Vertex(2));
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= zero && d2 >= zero && d6 <= zero {
+        return (a + ac * (d2 / (d2 - d6)), Feature::Edge(2));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= zero && d4 - d3 >= zero && d5 - d6 >= zero {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (b + (c - b) * w, Feature::Edge(1));
+    }
+
+    let denom = T::one() / (va + vb + vc);
+    (a + ab * (vb * denom) + ac * (vc * denom), Feature::Face)
+}
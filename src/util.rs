@@ -1,5 +1,5 @@
 //! Other random utilities that are helpful when using SDFs in computer graphics applications,
-//! such as estimating normals.
+//! such as estimating normals and computing soft shadows and ambient occlusion.
 use super::*;
 use std::ops::*;
 
@@ -195,3 +195,152 @@ impl<V: Vec<f64>> Default for TetrahedralEstimator<f64, V> {
         Self::new(0.001)
     }
 }
+
+/// The maximum number of steps taken along a shadow ray by `soft_shadow` and
+/// `soft_shadow_improved`.
+pub const MAX_SHADOW_STEPS: usize = 256;
+
+/// Computes a soft shadow factor for the point `origin` by marching a ray through `sdf`
+/// towards a light in direction `dir`.
+///
+/// The ray is marched from `mint` to `maxt`, and at every step the closest the ray passes to
+/// a surface, relative to how far along the ray it is, darkens the penumbra. `k` controls
+/// the hardness of the shadow; larger values create harder shadows. 8 is a good default value
+/// to try. Returns a value between 0 (fully shadowed) and 1 (fully lit).
+///
+/// See [this article](http://iquilezles.org/www/articles/rmshadows/rmshadows.htm)
+/// for more.
+pub fn soft_shadow<T, V, S>(sdf: S, origin: V, dir: V, mint: T, maxt: T, k: T) -> T
+where
+    T: Add<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Zero
+        + One
+        + MaxMin
+        + Clamp
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec3<T>,
+    S: SDF<T, V>,
+{
+    let eps = T::from(0.0001);
+    let mut res = T::one();
+    let mut t = mint;
+    let mut active = T::Mask::splat(true);
+
+    for _ in 0..MAX_SHADOW_STEPS {
+        if !active.any() {
+            break;
+        }
+        let h = sdf.dist(origin + dir * t);
+
+        let occluded = active & h.cmp_lt(eps);
+        res = T::select(active, res.min(k * h / t), res);
+        res = T::select(occluded, T::zero(), res);
+        active = active & !occluded;
+
+        t = T::select(active, t + h, t);
+        active = active & t.cmp_lt(maxt);
+    }
+
+    res.clamp(T::zero(), T::one())
+}
+
+/// Computes a soft shadow factor like `soft_shadow`, but estimates the closest distance
+/// between the shadow ray and the occluding surface by triangulating the distances of two
+/// consecutive steps. This removes the banding artifacts that `soft_shadow` creates at
+/// sharp corners.
+///
+/// See [this article](http://iquilezles.org/www/articles/rmshadows/rmshadows.htm)
+/// for more.
+pub fn soft_shadow_improved<T, V, S>(sdf: S, origin: V, dir: V, mint: T, maxt: T, k: T) -> T
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Zero
+        + One
+        + PointFive
+        + MaxMin
+        + Clamp
+        + Sqrt
+        + Compare
+        + From<f32>
+        + Copy,
+    V: Vec3<T>,
+    S: SDF<T, V>,
+{
+    let eps = T::from(0.0001);
+    let mut res = T::one();
+    let mut t = mint;
+    let mut ph = T::from(1e20);
+    let mut active = T::Mask::splat(true);
+
+    for _ in 0..MAX_SHADOW_STEPS {
+        if !active.any() {
+            break;
+        }
+        let h = sdf.dist(origin + dir * t);
+
+        let y = h * h * T::point_five() / ph;
+        let d = (h * h - y * y).max(T::zero()).sqrt();
+        let occluded = active & h.cmp_lt(eps);
+        res = T::select(active, res.min(k * d / (t - y).max(T::zero())), res);
+        res = T::select(occluded, T::zero(), res);
+        active = active & !occluded;
+
+        ph = T::select(active, h, ph);
+        t = T::select(active, t + h, t);
+        active = active & t.cmp_lt(maxt);
+    }
+
+    res.clamp(T::zero(), T::one())
+}
+
+/// Computes an ambient occlusion factor for the point `p` on the surface of `sdf` with
+/// normal `n`.
+///
+/// `samples` points are taken along the normal, spaced `step` apart. At each of them, the
+/// difference between the distance from `p` and the distance to the SDF measures how much
+/// nearby geometry occludes `p`, with closer samples weighted more. 5 samples spaced 0.02
+/// apart is a good default to try. Returns a value between 0 (fully occluded) and 1
+/// (not occluded).
+///
+/// See [this presentation](http://iquilezles.org/www/material/nvscene2008/rwwtt.pdf)
+/// for more.
+pub fn ambient_occlusion<T, V, S>(sdf: S, p: V, n: V, samples: usize, step: T) -> T
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Zero
+        + One
+        + PointFive
+        + Clamp
+        + Copy,
+    V: Vec3<T>,
+    S: SDF<T, V>,
+{
+    if samples == 0 {
+        return T::one();
+    }
+
+    let mut occlusion = T::zero();
+    let mut total_weight = T::zero();
+    let mut weight = T::one();
+    let mut h = T::zero();
+
+    for _ in 0..samples {
+        h = h + step;
+        let d = sdf.dist(p + n * h);
+        occlusion = occlusion + weight * (h - d) / h;
+        total_weight = total_weight + weight;
+        weight = weight * T::point_five();
+    }
+
+    (T::one() - occlusion / total_weight).clamp(T::zero(), T::one())
+}
@@ -0,0 +1,35 @@
+//! Helpers shared between the integration tests.
+#![allow(dead_code)]
+use ultraviolet::{Vec2, Vec3};
+
+/// Asserts that two distances agree to within `1e-4`.
+pub fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-4,
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+/// `count` points spread deterministically through the box from `-extent` to `extent`.
+pub fn sample_points_in(count: usize, extent: Vec3) -> impl Iterator<Item = Vec3> {
+    (0..count).map(move |i| {
+        let f = i as f32;
+        Vec3::new(
+            (f * 0.37).sin() * extent.x,
+            (f * 0.53).cos() * extent.y,
+            (f * 0.71).sin() * extent.z,
+        )
+    })
+}
+
+/// `count` points spread deterministically through the cube from `-extent` to `extent`.
+pub fn sample_points(count: usize, extent: f32) -> impl Iterator<Item = Vec3> {
+    sample_points_in(count, Vec3::broadcast(extent))
+}
+
+/// `count` points spread deterministically through the square from `-extent` to `extent`.
+pub fn sample_points_2d(count: usize, extent: f32) -> impl Iterator<Item = Vec2> {
+    sample_points(count, extent).map(|p| Vec2::new(p.x, p.y))
+}
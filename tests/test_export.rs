@@ -0,0 +1,92 @@
+#[cfg(feature = "ultraviolet")]
+fn sphere_mesh() -> sdfu::meshing::Mesh<ultraviolet::Vec3> {
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Sphere::new(1.0);
+    let mut mesh =
+        sdfu::meshing::surface_nets(sdf, Vec3::broadcast(-1.5), Vec3::broadcast(1.5), [6, 6, 6]);
+    mesh.estimate_normals(sdf, &sdfu::util::CentralDifferenceEstimator::new(0.001));
+    mesh.triangles.push([0, 1, 2]);
+    mesh
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_write_obj() {
+    let mesh = sphere_mesh();
+    let mut out = Vec::new();
+    sdfu::export::write_obj(&mesh, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let count = |prefix: &str| text.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(count("v "), mesh.vertices.len());
+    assert_eq!(count("vn "), mesh.vertices.len());
+    assert_eq!(count("f "), mesh.triangles.len() + mesh.quads.len());
+    assert!(text.contains("f 1//1 2//2 3//3\n"));
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_write_stl() {
+    let mesh = sphere_mesh();
+    let triangles = mesh.triangles.len() + 2 * mesh.quads.len();
+
+    let mut out = Vec::new();
+    sdfu::export::write_stl_binary(&mesh, &mut out).unwrap();
+    assert_eq!(out.len(), 84 + 50 * triangles);
+    assert_eq!(
+        u32::from_le_bytes([out[80], out[81], out[82], out[83]]) as usize,
+        triangles
+    );
+
+    let mut out = Vec::new();
+    sdfu::export::write_stl_ascii(&mesh, "sphere", &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.starts_with("solid sphere\n"));
+    assert!(text.ends_with("endsolid sphere\n"));
+    assert_eq!(text.matches("facet normal").count(), triangles);
+    assert_eq!(text.matches("vertex ").count(), 3 * triangles);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_write_ply() {
+    let mesh = sphere_mesh();
+    let faces = mesh.triangles.len() + mesh.quads.len();
+
+    let mut out = Vec::new();
+    sdfu::export::write_ply_ascii(&mesh, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.starts_with("ply\nformat ascii 1.0\n"));
+    assert!(text.contains(&format!("element vertex {}\n", mesh.vertices.len())));
+    assert!(text.contains(&format!("element face {}\n", faces)));
+    let body = text.split("end_header\n").nth(1).unwrap();
+    assert_eq!(body.lines().count(), mesh.vertices.len() + faces);
+
+    let mut out = Vec::new();
+    sdfu::export::write_ply_binary(&mesh, &mut out).unwrap();
+    let header_end = out.windows(11).position(|w| w == b"end_header\n").unwrap() + 11;
+    let header = std::str::from_utf8(&out[..header_end]).unwrap();
+    assert!(header.contains("format binary_little_endian 1.0\n"));
+    let body = out.len() - header_end;
+    assert_eq!(
+        body,
+        mesh.vertices.len() * 24 + mesh.triangles.len() * 13 + mesh.quads.len() * 17
+    );
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_write_error() {
+    struct Failing;
+    impl std::io::Write for Failing {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let mesh = sphere_mesh();
+    assert!(sdfu::export::write_obj(&mesh, Failing).is_err());
+    assert!(sdfu::export::write_stl_binary(&mesh, Failing).is_err());
+    assert!(sdfu::export::write_ply_binary(&mesh, Failing).is_err());
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "ultraviolet")]
+mod common;
+#[cfg(feature = "ultraviolet")]
+use common::sample_points;
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_grid_matches_source() {
+    use sdfu::grid::{Grid3, Interpolation};
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let source = sdfu::Sphere::new(1.0).union(sdfu::Box::new(Vec3::new(1.2, 0.3, 0.3)));
+    let (min, max) = (Vec3::broadcast(-1.5), Vec3::broadcast(1.5));
+    let trilinear = Grid3::bake(source, min, max, [30, 30, 30], Interpolation::Trilinear);
+    let tricubic = Grid3 {
+        interpolation: Interpolation::Tricubic,
+        ..trilinear.clone()
+    };
+
+    // Grid points are reproduced exactly.
+    let p = Vec3::new(0.2, -0.5, 1.0);
+    assert!(((&trilinear).dist(p) - source.dist(p)).abs() < 1e-5);
+    assert!(((&tricubic).dist(p) - source.dist(p)).abs() < 1e-5);
+
+    for p in sample_points(200, 1.4) {
+        let d = source.dist(p);
+        assert!(((&trilinear).dist(p) - d).abs() < 0.05);
+        assert!(((&tricubic).dist(p) - d).abs() < 0.05);
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_grid_outside_is_conservative() {
+    use sdfu::grid::{Grid3, Interpolation};
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let source = sdfu::Sphere::new(1.0);
+    let grid = Grid3::bake(
+        source,
+        Vec3::broadcast(-1.25),
+        Vec3::broadcast(1.25),
+        [10, 10, 10],
+        Interpolation::Trilinear,
+    );
+    for p in [
+        Vec3::new(3.0, 0.0, 0.0),
+        Vec3::new(2.0, 2.0, -2.0),
+        Vec3::new(0.0, -1.3, 0.1),
+    ] {
+        let d = (&grid).dist(p);
+        assert!(d > 0.0 && d <= source.dist(p) + 1e-5);
+    }
+
+    let sdf = sdfu::Box::new(Vec3::broadcast(3.0)).subtract(&grid);
+    assert!(sdf.dist(Vec3::zero()) > 0.0);
+    assert!(sdf.dist(Vec3::new(2.0, 0.0, 0.0)) < 0.0);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_octree_matches_source() {
+    use sdfu::grid::Octree;
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let source = sdfu::Sphere::new(1.0).union(sdfu::Box::new(Vec3::new(1.2, 0.3, 0.3)));
+    let (min, max) = (Vec3::broadcast(-2.0), Vec3::broadcast(2.0));
+    let octree = Octree::bake(source, min, max, 6, None);
+    let coarse = Octree::bake(source, min, max, 6, Some(0.001));
+    // A dense grid of the same resolution would need 65^3 samples.
+    assert!(octree.node_count() < 65 * 65 * 65 / 8);
+    assert!(coarse.node_count() < octree.node_count());
+
+    for p in sample_points(400, 2.5) {
+        let d = source.dist(p);
+        for sdf in [&octree, &coarse] {
+            let baked = sdf.dist(p);
+            // Close to the surface the distance is interpolated, and further away it is
+            // a conservative bound with the same sign.
+            if d.abs() < 0.1 {
+                assert!((baked - d).abs() < 0.02, "{:?}: {} vs {}", p, baked, d);
+            } else {
+                assert!(baked.signum() == d.signum() && baked.abs() <= d.abs() + 0.02);
+            }
+        }
+    }
+}
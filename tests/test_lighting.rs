@@ -0,0 +1,39 @@
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_soft_shadow() {
+    use sdfu::util::{soft_shadow, soft_shadow_improved};
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Sphere::new(1.0).translate(Vec3::new(0.0, 3.0, 0.0));
+    let up = Vec3::new(0.0, 1.0, 0.0);
+
+    let shadowed = soft_shadow(sdf, Vec3::zero(), up, 0.01, 10.0, 8.0);
+    assert_eq!(shadowed, 0.0);
+    let lit = soft_shadow(sdf, Vec3::new(5.0, 0.0, 0.0), up, 0.01, 10.0, 8.0);
+    assert_eq!(lit, 1.0);
+    let penumbra = soft_shadow(sdf, Vec3::new(1.1, 0.0, 0.0), up, 0.01, 10.0, 8.0);
+    assert!(penumbra > 0.0 && penumbra < 1.0);
+
+    let shadowed = soft_shadow_improved(sdf, Vec3::zero(), up, 0.01, 10.0, 8.0);
+    assert_eq!(shadowed, 0.0);
+    let penumbra = soft_shadow_improved(sdf, Vec3::new(1.1, 0.0, 0.0), up, 0.01, 10.0, 8.0);
+    assert!(penumbra > 0.0 && penumbra < 1.0);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_ambient_occlusion() {
+    use sdfu::util::ambient_occlusion;
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let floor = sdfu::Box::new(Vec3::new(10.0, 1.0, 10.0)).translate(Vec3::new(0.0, -1.0, 0.0));
+    let up = Vec3::new(0.0, 1.0, 0.0);
+
+    let open = ambient_occlusion(floor, Vec3::zero(), up, 5, 0.02);
+    assert!((open - 1.0).abs() < 1e-4);
+
+    let corner =
+        floor.union(sdfu::Box::new(Vec3::new(1.0, 2.0, 10.0)).translate(Vec3::new(1.01, 0.0, 0.0)));
+    let occluded = ambient_occlusion(corner, Vec3::zero(), up, 5, 0.02);
+    assert!(occluded < open);
+}
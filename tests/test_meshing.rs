@@ -0,0 +1,220 @@
+#[cfg(feature = "ultraviolet")]
+fn assert_watertight(triangles: &[[u32; 3]]) {
+    use std::collections::HashMap;
+    let mut edges = HashMap::new();
+    for tri in triangles {
+        for i in 0..3 {
+            *edges.entry((tri[i], tri[(i + 1) % 3])).or_insert(0) += 1;
+        }
+    }
+    for (&(a, b), &count) in edges.iter() {
+        assert_eq!(count, 1, "edge {:?} used more than once", (a, b));
+        assert_eq!(
+            edges.get(&(b, a)),
+            Some(&1),
+            "edge {:?} has no twin",
+            (a, b)
+        );
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+fn signed_volume(vertices: &[ultraviolet::Vec3], triangles: &[[u32; 3]]) -> f32 {
+    triangles
+        .iter()
+        .map(|t| {
+            let (a, b, c) = (
+                vertices[t[0] as usize],
+                vertices[t[1] as usize],
+                vertices[t[2] as usize],
+            );
+            a.dot(b.cross(c)) / 6.0
+        })
+        .sum()
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_marching_cubes_sphere() {
+    use sdfu::meshing::marching_cubes;
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Sphere::new(1.0);
+    let mut mesh = marching_cubes(
+        sdf,
+        Vec3::broadcast(-1.5),
+        Vec3::broadcast(1.5),
+        [24, 24, 24],
+    );
+    assert!(!mesh.triangles.is_empty());
+    assert_watertight(&mesh.triangles);
+    for v in mesh.vertices.iter() {
+        assert!(sdf.dist(*v).abs() < 0.02);
+    }
+    let volume = signed_volume(&mesh.vertices, &mesh.triangles);
+    assert!((volume - 4.0 / 3.0 * std::f32::consts::PI).abs() < 0.1);
+
+    mesh.estimate_normals(sdf, &sdfu::util::CentralDifferenceEstimator::new(0.001));
+    let normals = mesh.normals.unwrap();
+    for (v, n) in mesh.vertices.iter().zip(normals.iter()) {
+        assert!(v.normalized().dot(*n) > 0.99);
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_marching_cubes_composed() {
+    use sdfu::meshing::marching_cubes;
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Box::new(Vec3::new(0.6, 0.4, 0.5))
+        .union(sdfu::Torus::new(0.2, 0.7))
+        .subtract(sdfu::Sphere::new(0.3).translate(Vec3::new(0.3, 0.3, 0.3)));
+    let mesh = marching_cubes(
+        sdf,
+        Vec3::broadcast(-1.2),
+        Vec3::broadcast(1.2),
+        [31, 27, 29],
+    );
+    assert_watertight(&mesh.triangles);
+    assert!(signed_volume(&mesh.vertices, &mesh.triangles) > 0.0);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_surface_nets_sphere() {
+    use sdfu::meshing::surface_nets;
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Sphere::new(1.0);
+    let mut mesh = surface_nets(
+        sdf,
+        Vec3::broadcast(-1.5),
+        Vec3::broadcast(1.5),
+        [24, 24, 24],
+    );
+    assert!(!mesh.quads.is_empty());
+    mesh.triangulate();
+    assert!(mesh.quads.is_empty());
+    assert_watertight(&mesh.triangles);
+    for v in mesh.vertices.iter() {
+        assert!(sdf.dist(*v).abs() < 0.05);
+    }
+    let volume = signed_volume(&mesh.vertices, &mesh.triangles);
+    assert!((volume - 4.0 / 3.0 * std::f32::consts::PI).abs() < 0.15);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_dual_contouring_sharp_corners() {
+    use sdfu::meshing::dual_contouring;
+    use sdfu::util::CentralDifferenceEstimator;
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let sdf =
+        sdfu::Box::new(Vec3::broadcast(0.5)).rotate(ultraviolet::Rotor3::from_rotation_xz(0.3));
+    let mut mesh = dual_contouring(
+        sdf,
+        Vec3::broadcast(-1.0),
+        Vec3::broadcast(1.0),
+        [17, 17, 17],
+        &CentralDifferenceEstimator::new(0.0001),
+    );
+    mesh.triangulate();
+    assert_watertight(&mesh.triangles);
+    for v in mesh.vertices.iter() {
+        assert!(sdf.dist(*v).abs() < 0.01);
+    }
+    // Every corner of the box should be reproduced by some vertex.
+    let rotor = ultraviolet::Rotor3::from_rotation_xz(0.3);
+    for corner in 0..8 {
+        let local = Vec3::new(
+            if corner & 1 == 0 { -0.5 } else { 0.5 },
+            if corner & 2 == 0 { -0.5 } else { 0.5 },
+            if corner & 4 == 0 { -0.5 } else { 0.5 },
+        );
+        let world = rotor * local;
+        let closest = mesh
+            .vertices
+            .iter()
+            .map(|v| (*v - world).mag())
+            .fold(f32::MAX, f32::min);
+        assert!(closest < 0.02, "corner {:?} off by {}", world, closest);
+    }
+    let volume = signed_volume(&mesh.vertices, &mesh.triangles);
+    assert!((volume - 1.0).abs() < 0.02, "volume {}", volume);
+}
+
+#[cfg(feature = "ultraviolet")]
+fn signed_area(points: &[ultraviolet::Vec2]) -> f32 {
+    (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            (a.x * b.y - b.x * a.y) / 2.0
+        })
+        .sum()
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_marching_squares_ring() {
+    use sdfu::meshing::marching_squares;
+    use sdfu::SDF;
+    use ultraviolet::Vec2;
+    let ring = sdfu::ops::Subtraction::new(sdfu::Circle::new(0.5), sdfu::Circle::new(1.0));
+    let contours = marching_squares(
+        ring,
+        Vec2::broadcast(-1.5),
+        Vec2::broadcast(1.5),
+        [40, 37],
+        0.0,
+    );
+    assert_eq!(contours.len(), 2);
+    let mut areas: Vec<f32> = contours
+        .iter()
+        .map(|c| {
+            assert!(c.closed);
+            for p in c.points.iter() {
+                assert!(ring.dist(*p).abs() < 0.01);
+            }
+            signed_area(&c.points)
+        })
+        .collect();
+    areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    // The hole runs clockwise and the outer boundary counter-clockwise.
+    assert!((areas[0] + std::f32::consts::PI * 0.25).abs() < 0.05);
+    assert!((areas[1] - std::f32::consts::PI).abs() < 0.05);
+
+    let offset = marching_squares(
+        ring,
+        Vec2::broadcast(-1.5),
+        Vec2::broadcast(1.5),
+        [40, 40],
+        0.2,
+    );
+    assert_eq!(offset.len(), 2);
+    for p in offset.iter().flat_map(|c| c.points.iter()) {
+        assert!((ring.dist(*p) - 0.2).abs() < 0.01);
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_marching_squares_open() {
+    use sdfu::meshing::marching_squares;
+    use ultraviolet::Vec2;
+    let sdf = sdfu::Circle::new(1.0);
+    let contours = marching_squares(
+        sdf,
+        Vec2::new(0.0, -2.0),
+        Vec2::new(2.0, 2.0),
+        [20, 40],
+        0.0,
+    );
+    assert_eq!(contours.len(), 1);
+    let contour = &contours[0];
+    assert!(!contour.closed);
+    // With the inside on the left, the right half of the circle is traced bottom to top.
+    assert!(contour.points[0].y < 0.0);
+    assert!(contour.points.last().unwrap().y > 0.0);
+}
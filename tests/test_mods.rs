@@ -0,0 +1,105 @@
+#[cfg(feature = "ultraviolet")]
+mod common;
+#[cfg(feature = "ultraviolet")]
+use common::{assert_close, sample_points};
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_extrude() {
+    use sdfu::{Axis, CappedCylinder, Circle, Dim2D, Dim3D, SDF};
+    use ultraviolet::{f32x4, Vec2, Vec2x4, Vec3, Vec3x4};
+    let profile = sdfu::Box::<Vec2, Dim2D>::new(Vec2::new(1.0, 0.5));
+    let cases = [
+        (Axis::X, Vec3::new(0.75, 1.0, 0.5)),
+        (Axis::Y, Vec3::new(1.0, 0.75, 0.5)),
+        (Axis::Z, Vec3::new(1.0, 0.5, 0.75)),
+    ];
+    for (axis, dims) in cases {
+        let extruded = profile.extrude(axis, 0.75);
+        let reference = sdfu::Box::<Vec3, Dim3D>::new(dims);
+        for p in sample_points(400, 2.0) {
+            assert_close(extruded.dist(p), reference.dist(p));
+        }
+    }
+
+    let cylinder = SDF::<f32, Vec2>::extrude(Circle::new(1.0), Axis::Y, 0.5);
+    let reference = CappedCylinder::new(1.0, 0.5, Axis::Y);
+    for p in sample_points(400, 2.0) {
+        assert_close(cylinder.dist(p), reference.dist(p));
+    }
+
+    let w = f32x4::from;
+    let wide =
+        sdfu::Box::<Vec2x4, Dim2D>::new(Vec2x4::new(w(1.0), w(0.5))).extrude(Axis::Z, w(0.75));
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, -3.0, 0.4),
+        Vec3::new(-0.5, 0.1, 0.0),
+    ];
+    let lanes: [f32; 4] = wide.dist(Vec3x4::from(points)).into();
+    let scalar = profile.extrude(Axis::Z, 0.75);
+    for (&p, &d) in points.iter().zip(lanes.iter()) {
+        assert_close(d, scalar.dist(p));
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_revolve() {
+    use sdfu::{Axis, CappedCylinder, Circle, Dim2D, Torus, SDF};
+    use std::f32::consts::FRAC_PI_2;
+    use ultraviolet::{f32x4, Vec2, Vec2x4, Vec3, Vec3x4};
+    let circle = |radius| -> sdfu::Circle<f32> { Circle::new(radius) };
+    let torus = SDF::<f32, Vec2>::revolve(circle(0.5), Axis::Y, 1.5);
+    let cylinder = sdfu::Box::<Vec2, Dim2D>::new(Vec2::new(1.0, 0.5)).revolve(Axis::Z, 0.0);
+    for p in sample_points(400, 2.0) {
+        assert_close(torus.dist(p), Torus::new(0.5, 1.5).dist(p));
+        assert_close(
+            cylinder.dist(p),
+            CappedCylinder::new(1.0, 0.5, Axis::Z).dist(p),
+        );
+        // Around X, the profile's X axis points away from X in the YZ plane.
+        let around_x = SDF::<f32, Vec2>::revolve(circle(0.5), Axis::X, 1.5);
+        let q = Vec3::new(p.y, p.x, p.z);
+        assert_close(around_x.dist(q), torus.dist(p));
+    }
+
+    // The solid is made up of the copies of the profile lying in each half-plane between the
+    // two angles, so outside it the distance is the smallest distance to any of them.
+    for (start, end) in [(0.5f32, 1.5), (-1.0, 2.0), (FRAC_PI_2, 5.5)] {
+        let partial = SDF::<f32, Vec2>::revolve_partial(circle(0.5), Axis::Y, 1.5, start, end);
+        for p in sample_points(400, 2.0) {
+            let expected = (0..=2000)
+                .map(|i| {
+                    let (sin, cos) = (start + (end - start) * i as f32 / 2000.0).sin_cos();
+                    let (u, h) = (p.x * cos + p.z * sin, p.z * cos - p.x * sin);
+                    let d = circle(0.5).dist(Vec2::new(u - 1.5, p.y)).max(0.0);
+                    (d * d + h * h).sqrt()
+                })
+                .fold(f32::INFINITY, f32::min);
+            let d = partial.dist(p);
+            // Sampling the angles leaves gaps of up to 2e-3 between the copies.
+            if expected > 2e-3 {
+                assert!((d - expected).abs() < 1e-3);
+            } else {
+                assert!(d <= expected + 1e-4 && d >= torus.dist(p) - 1e-4);
+            }
+        }
+    }
+
+    let w = f32x4::from;
+    let points = [
+        Vec3::new(0.3, -0.2, 1.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, -3.0, 0.4),
+        Vec3::new(-1.5, 0.1, 0.2),
+    ];
+    let scalar = SDF::<f32, Vec2>::revolve_partial(circle(0.5), Axis::Y, 1.5, 0.5, 2.5);
+    let wide =
+        SDF::<f32x4, Vec2x4>::revolve_partial(Circle::new(w(0.5)), Axis::Y, w(1.5), w(0.5), w(2.5));
+    let lanes: [f32; 4] = wide.dist(Vec3x4::from(points)).into();
+    for (&p, &d) in points.iter().zip(lanes.iter()) {
+        assert_close(d, scalar.dist(p));
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "ultraviolet")]
+mod common;
+#[cfg(feature = "ultraviolet")]
+use common::sample_points_2d;
+
+#[cfg(feature = "ultraviolet")]
+fn square(half: f32, clockwise: bool) -> Vec<ultraviolet::Vec2> {
+    use ultraviolet::Vec2;
+    let mut ring = vec![
+        Vec2::new(half, half),
+        Vec2::new(-half, half),
+        Vec2::new(-half, -half),
+        Vec2::new(half, -half),
+    ];
+    if clockwise {
+        ring.reverse();
+    }
+    ring
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_matches_box() {
+    use sdfu::polygon::Polygon2D;
+    use sdfu::{Dim2D, SDF};
+    use ultraviolet::Vec2;
+    let reference = sdfu::Box::<Vec2, Dim2D>::new(Vec2::broadcast(1.0));
+    for clockwise in [false, true] {
+        let polygon = Polygon2D::new(square(1.0, clockwise));
+        for p in sample_points_2d(400, 2.5) {
+            assert!(((&polygon).dist(p) - reference.dist(p)).abs() < 1e-5);
+        }
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_holes_and_fill_rules() {
+    use sdfu::polygon::{FillRule, Polygon2D};
+    use sdfu::SDF;
+    use ultraviolet::Vec2;
+    let even_odd = Polygon2D::from_rings(
+        vec![square(1.0, false), square(0.5, false)],
+        FillRule::EvenOdd,
+    );
+    let opposite = Polygon2D::from_rings(
+        vec![square(1.0, false), square(0.5, true)],
+        FillRule::NonZero,
+    );
+    let same = Polygon2D::from_rings(
+        vec![square(1.0, false), square(0.5, false)],
+        FillRule::NonZero,
+    );
+    for (polygon, hole) in [(&even_odd, true), (&opposite, true), (&same, false)] {
+        let center = polygon.dist(Vec2::zero());
+        assert!((center.abs() - 0.5).abs() < 1e-6);
+        assert_eq!(center > 0.0, hole);
+        assert!((polygon.dist(Vec2::new(0.75, 0.0)) + 0.25).abs() < 1e-6);
+        assert!((polygon.dist(Vec2::new(2.0, 0.0)) - 1.0).abs() < 1e-6);
+    }
+
+    // A self-intersecting pentagram, whose center is wound around twice.
+    let star: Vec<Vec2> = (0..5)
+        .map(|i| {
+            let theta = std::f32::consts::PI * 0.8 * i as f32;
+            Vec2::new(theta.sin(), theta.cos())
+        })
+        .collect();
+    let even_odd = Polygon2D::from_rings(vec![star.clone()], FillRule::EvenOdd);
+    let non_zero = Polygon2D::from_rings(vec![star], FillRule::NonZero);
+    assert!((&even_odd).dist(Vec2::zero()) > 0.0);
+    assert!((&non_zero).dist(Vec2::zero()) < 0.0);
+    assert!((&even_odd).dist(Vec2::new(0.0, 0.9)) < 0.0);
+    assert!((&non_zero).dist(Vec2::new(0.0, 0.9)) < 0.0);
+    assert!((&non_zero).dist(Vec2::new(0.0, 1.1)) > 0.0);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_lanes_match() {
+    use sdfu::polygon::{FillRule, Polygon2D};
+    use sdfu::SDF;
+    use ultraviolet::{Vec2, Vec2x4};
+    let rings = vec![square(1.0, false), square(0.5, true)];
+    let wide_rings: Vec<Vec<Vec2x4>> = rings
+        .iter()
+        .map(|ring| ring.iter().map(|&v| Vec2x4::splat(v)).collect())
+        .collect();
+    for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+        let scalar = Polygon2D::from_rings(rings.clone(), fill_rule);
+        let wide = Polygon2D::from_rings(wide_rings.clone(), fill_rule);
+        let points = [
+            Vec2::new(0.0, 0.1),
+            Vec2::new(0.75, 0.5),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(-3.0, 2.0),
+        ];
+        let lanes: [f32; 4] = (&wide).dist(Vec2x4::from(points)).into();
+        for (&p, &d) in points.iter().zip(lanes.iter()) {
+            assert!((d - (&scalar).dist(p)).abs() < 1e-6);
+        }
+    }
+}
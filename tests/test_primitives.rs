@@ -0,0 +1,964 @@
+#[cfg(feature = "ultraviolet")]
+mod common;
+#[cfg(feature = "ultraviolet")]
+use common::{assert_close, sample_points, sample_points_2d, sample_points_in};
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_planes() {
+    use sdfu::{AxisPlane, Dim2D, Dim3D, Plane, SDF};
+    use ultraviolet::{Vec2, Vec3};
+    let floor = Plane::new(Vec3::unit_y(), -1.0);
+    assert_eq!(floor.dist(Vec3::new(3.0, 1.0, -2.0)), 2.0);
+    assert_eq!(floor.dist(Vec3::new(0.0, -1.5, 0.0)), -0.5);
+
+    let tilted = Plane::new(Vec2::new(1.0, 1.0).normalized(), 0.0);
+    assert!((tilted.dist(Vec2::new(1.0, 1.0)) - 2f32.sqrt()).abs() < 1e-6);
+
+    let axis: AxisPlane<f32, Dim3D> = AxisPlane::new(sdfu::Axis::Z, 0.5);
+    assert_eq!(axis.dist(Vec3::new(4.0, 4.0, 2.0)), 1.5);
+    let axis: AxisPlane<f32, Dim2D> = AxisPlane::new(sdfu::Axis::X, 0.5);
+    assert_eq!(axis.dist(Vec2::new(-1.0, 7.0)), -1.5);
+
+    // Clipping a sphere to its lower half.
+    let clipped = SDF::<f32, Vec3>::intersection(
+        sdfu::Sphere::new(1.0),
+        AxisPlane::<_, Dim3D>::new(sdfu::Axis::Y, 0.0),
+    );
+    assert_eq!(clipped.dist(Vec3::new(0.0, 0.5, 0.0)), 0.5);
+    assert_eq!(clipped.dist(Vec3::new(0.0, -0.5, 0.0)), -0.5);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+#[should_panic]
+fn test_axis_plane_2d_z() {
+    use sdfu::{AxisPlane, Dim2D, SDF};
+    let plane: AxisPlane<f32, Dim2D> = AxisPlane::new(sdfu::Axis::Z, 0.0);
+    plane.dist(ultraviolet::Vec2::zero());
+}
+
+#[cfg(feature = "ultraviolet")]
+fn assert_lanes_match<S, W>(scalar: S, wide: W, points: [ultraviolet::Vec3; 4])
+where
+    S: sdfu::SDF<f32, ultraviolet::Vec3>,
+    W: sdfu::SDF<ultraviolet::f32x4, ultraviolet::Vec3x4>,
+{
+    let lanes: [f32; 4] = wide.dist(ultraviolet::Vec3x4::from(points)).into();
+    for (&p, &d) in points.iter().zip(lanes.iter()) {
+        assert_close(d, scalar.dist(p));
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_cones() {
+    use sdfu::{Axis, CappedCone, Cone, RoundCone, SolidAngle, SDF};
+    use ultraviolet::{f32x4, Vec3};
+    let cone = Cone::new(1.0, 1.0, Axis::Y);
+    assert_close(cone.dist(Vec3::new(0.0, -2.0, 0.0)), 1.0);
+    assert_close(cone.dist(Vec3::new(0.0, 3.0, 0.0)), 2.0);
+    assert_close(cone.dist(Vec3::zero()), -1.0 / 5f32.sqrt());
+    assert_close(
+        Cone::new(1.0, 1.0, Axis::X).dist(Vec3::new(3.0, 0.0, 0.0)),
+        2.0,
+    );
+
+    let capped = CappedCone::new(1.0, 0.5, 1.0, Axis::Y);
+    assert_close(capped.dist(Vec3::new(0.0, 2.0, 0.0)), 1.0);
+    assert_close(capped.dist(Vec3::new(0.0, -1.5, 0.0)), 0.5);
+    assert_close(capped.dist(Vec3::new(3.0, 0.0, 0.0)), 4.5 / 4.25f32.sqrt());
+    assert!(capped.dist(Vec3::zero()) < 0.0);
+
+    let round = RoundCone::new(1.0, 0.5, 1.0, Axis::Z);
+    assert_close(round.dist(Vec3::new(0.0, 0.0, -3.0)), 1.0);
+    assert_close(round.dist(Vec3::new(0.0, 0.0, 3.0)), 1.5);
+    assert!(round.dist(Vec3::zero()) < 0.0);
+    // The bottom sphere contains the top one, so only the bottom one is left.
+    let contained = RoundCone::new(1.0, 0.2, 0.3, Axis::Z);
+    for p in sample_points(200, 2.0) {
+        let bottom = (p - Vec3::new(0.0, 0.0, -0.3)).mag() - 1.0;
+        assert_close(contained.dist(p), bottom);
+    }
+
+    let solid = SolidAngle::new(std::f32::consts::FRAC_PI_4, 1.0, Axis::Y);
+    assert_close(solid.dist(Vec3::new(0.0, 2.0, 0.0)), 1.0);
+    assert_close(solid.dist(Vec3::new(0.0, 0.5, 0.0)), -0.5f32.sqrt() / 2.0);
+    assert_close(solid.dist(Vec3::new(1.0, 0.0, 0.0)), 0.5f32.sqrt());
+
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, -3.0, 0.2),
+        Vec3::new(-0.5, 1.5, 0.5),
+    ];
+    let w = f32x4::from;
+    assert_lanes_match(cone, Cone::new(w(1.0), w(1.0), Axis::Y), points);
+    assert_lanes_match(
+        capped,
+        CappedCone::new(w(1.0), w(0.5), w(1.0), Axis::Y),
+        points,
+    );
+    assert_lanes_match(
+        round,
+        RoundCone::new(w(1.0), w(0.5), w(1.0), Axis::Z),
+        points,
+    );
+    assert_lanes_match(
+        contained,
+        RoundCone::new(w(1.0), w(0.2), w(0.3), Axis::Z),
+        points,
+    );
+    assert_lanes_match(
+        solid,
+        SolidAngle::new(w(std::f32::consts::FRAC_PI_4), w(1.0), Axis::Y),
+        points,
+    );
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_ellipsoid() {
+    use sdfu::{Ellipsoid, SDF};
+    use ultraviolet::{f32x4, Vec3, Vec3x4};
+    let radii = Vec3::new(3.0, 2.0, 1.0);
+    let bound = Ellipsoid::new(radii);
+    let exact = bound.exact();
+    assert_close(exact.dist(Vec3::new(5.0, 0.0, 0.0)), 2.0);
+    assert_close(exact.dist(Vec3::new(0.0, 0.0, -0.5)), -0.5);
+    assert_close(exact.dist(Vec3::new(2.9, 0.0, 0.0)), -0.1);
+    assert_close(exact.dist(Vec3::new(1.0, 0.0, 0.0)), -(56f32 / 64.0).sqrt());
+    assert_close(bound.dist(Vec3::new(0.0, 0.0, 1.0)), 0.0);
+
+    let mut surface = Vec::new();
+    for i in 0..=200 {
+        for j in 0..400 {
+            let theta = std::f32::consts::PI * i as f32 / 200.0;
+            let phi = 2.0 * std::f32::consts::PI * j as f32 / 400.0;
+            surface.push(Vec3::new(
+                3.0 * theta.sin() * phi.cos(),
+                2.0 * theta.sin() * phi.sin(),
+                theta.cos(),
+            ));
+        }
+    }
+    for p in sample_points_in(50, Vec3::new(4.0, 3.0, 2.0)) {
+        let d = exact.dist(p);
+        let brute = surface
+            .iter()
+            .map(|&s| (s - p).mag())
+            .fold(f32::INFINITY, f32::min);
+        assert!(
+            (d.abs() - brute).abs() < 0.02,
+            "{:?}: {} vs {}",
+            p,
+            d,
+            brute
+        );
+        let b = bound.dist(p);
+        assert!(
+            b.signum() == d.signum() && b.abs() <= d.abs() + 1e-4,
+            "{:?}: {} vs {}",
+            p,
+            b,
+            d
+        );
+    }
+
+    let wide = Ellipsoid::new(Vec3x4::new(
+        f32x4::from(3.0),
+        f32x4::from(2.0),
+        f32x4::from(1.0),
+    ));
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, -3.0, 0.2),
+        Vec3::new(-0.5, 1.5, 0.5),
+    ];
+    assert_lanes_match(bound, wide, points);
+}
+
+/// Checks `sdf` against a triangle mesh of the same shape at points around it.
+#[cfg(feature = "ultraviolet")]
+fn assert_matches_mesh<S>(sdf: S, vertices: Vec<ultraviolet::Vec3>, triangles: Vec<[u32; 3]>)
+where
+    S: sdfu::SDF<f32, ultraviolet::Vec3>,
+{
+    use sdfu::SDF;
+    let mesh = sdfu::trimesh::TriangleMesh::new(vertices, triangles);
+    for p in sample_points(200, 2.0) {
+        assert_close(sdf.dist(p), (&mesh).dist(p));
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_polyhedra() {
+    use sdfu::{Axis, Dodecahedron, HexPrism, Icosahedron, Octahedron, Pyramid, TriPrism, SDF};
+    use ultraviolet::{f32x4, Vec3};
+    let octahedron = Octahedron::new(1.0);
+    assert_close(octahedron.dist(Vec3::new(2.0, 0.0, 0.0)), 1.0);
+    assert_close(octahedron.dist(Vec3::zero()), -1.0 / 3f32.sqrt());
+    assert_close(octahedron.dist(Vec3::broadcast(1.0)), 2.0 / 3f32.sqrt());
+    assert_matches_mesh(
+        octahedron,
+        vec![
+            Vec3::unit_x(),
+            -Vec3::unit_x(),
+            Vec3::unit_y(),
+            -Vec3::unit_y(),
+            Vec3::unit_z(),
+            -Vec3::unit_z(),
+        ],
+        vec![
+            [0, 2, 4],
+            [2, 1, 4],
+            [1, 3, 4],
+            [3, 0, 4],
+            [2, 0, 5],
+            [1, 2, 5],
+            [3, 1, 5],
+            [0, 3, 5],
+        ],
+    );
+
+    let hex = HexPrism::new(1.0, 1.0, Axis::Z);
+    assert_close(hex.dist(Vec3::new(0.0, 2.0, 0.0)), 1.0);
+    assert_close(hex.dist(Vec3::new(0.0, 0.0, 3.0)), 2.0);
+    assert_close(hex.dist(Vec3::zero()), -1.0);
+    let corner = 2.0 / 3f32.sqrt();
+    assert_close(hex.dist(Vec3::new(corner + 1.0, 0.0, 0.0)), 1.0);
+
+    let tri = TriPrism::new(1.0, 1.0, Axis::Z);
+    let inradius = 1.0 / 3f32.sqrt();
+    assert_close(tri.dist(Vec3::new(0.0, -2.0, 0.0)), 2.0 - inradius);
+    assert_close(tri.dist(Vec3::zero()), -inradius);
+    let (top, bottom) = (2.0 * inradius, -inradius);
+    let mut vertices = Vec::new();
+    for &z in [-1.0, 1.0].iter() {
+        vertices.push(Vec3::new(-1.0, bottom, z));
+        vertices.push(Vec3::new(1.0, bottom, z));
+        vertices.push(Vec3::new(0.0, top, z));
+    }
+    assert_matches_mesh(
+        tri,
+        vertices,
+        vec![
+            [0, 2, 1],
+            [3, 4, 5],
+            [0, 1, 4],
+            [0, 4, 3],
+            [1, 2, 5],
+            [1, 5, 4],
+            [2, 0, 3],
+            [2, 3, 5],
+        ],
+    );
+
+    let pyramid = Pyramid::new(0.5, 1.0, Axis::Y);
+    assert_close(pyramid.dist(Vec3::new(0.0, 2.0, 0.0)), 1.0);
+    assert_close(pyramid.dist(Vec3::new(0.0, -1.0, 0.0)), 1.0);
+    assert!(pyramid.dist(Vec3::new(0.0, 0.5, 0.0)) < 0.0);
+    assert_matches_mesh(
+        Pyramid::new(1.0, 1.5, Axis::Y),
+        vec![
+            Vec3::new(-1.0, 0.0, -1.0),
+            Vec3::new(1.0, 0.0, -1.0),
+            Vec3::new(1.0, 0.0, 1.0),
+            Vec3::new(-1.0, 0.0, 1.0),
+            Vec3::new(0.0, 1.5, 0.0),
+        ],
+        vec![
+            [0, 1, 2],
+            [0, 2, 3],
+            [1, 0, 4],
+            [2, 1, 4],
+            [3, 2, 4],
+            [0, 3, 4],
+        ],
+    );
+
+    assert_close(Dodecahedron::new(1.0).dist(Vec3::zero()), -1.0);
+    assert_close(Icosahedron::new(1.0).dist(Vec3::zero()), -1.0);
+    assert_close(
+        Dodecahedron::new(1.0).dist(Vec3::new(0.0, 0.850_650_8, 0.525_731_1) * 3.0),
+        2.0,
+    );
+    assert_close(
+        Icosahedron::new(1.0).dist(Vec3::broadcast(3.0)),
+        3f32.sqrt() * 3.0 - 1.0,
+    );
+
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, -3.0, 0.2),
+        Vec3::new(-0.5, 1.5, 0.5),
+    ];
+    let w = f32x4::from;
+    assert_lanes_match(octahedron, Octahedron::new(w(1.0)), points);
+    assert_lanes_match(hex, HexPrism::new(w(1.0), w(1.0), Axis::Z), points);
+    assert_lanes_match(tri, TriPrism::new(w(1.0), w(1.0), Axis::Z), points);
+    assert_lanes_match(pyramid, Pyramid::new(w(0.5), w(1.0), Axis::Y), points);
+    assert_lanes_match(Icosahedron::new(1.0), Icosahedron::new(w(1.0)), points);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_frames_and_links() {
+    use sdfu::{BoxFrame, CappedTorus, Link, SDF};
+    use ultraviolet::{f32x4, Vec3, Vec3x4};
+    let frame = BoxFrame::new(Vec3::broadcast(1.0), 0.1);
+    assert_close(frame.dist(Vec3::zero()), 1.28f32.sqrt());
+    assert_close(frame.dist(Vec3::new(0.0, 0.9, 0.9)), -0.1);
+    assert_close(frame.dist(Vec3::new(2.0, 0.0, 0.0)), 1.64f32.sqrt());
+
+    let link = Link::new(1.0, 1.0, 0.2);
+    assert_close(link.dist(Vec3::zero()), 0.8);
+    assert_close(link.dist(Vec3::new(3.0, 0.0, 0.0)), 0.8);
+    assert_close(link.dist(Vec3::new(0.0, 0.0, 1.0)), -0.2);
+
+    let half = CappedTorus::new(1.0, 0.0, 1.0, 0.25);
+    assert_close(half.dist(Vec3::new(0.0, 0.0, 1.0)), -0.25);
+    assert_close(half.dist(Vec3::new(0.0, 0.0, -1.0)), 2f32.sqrt() - 0.25);
+    let full = CappedTorus::new(0.0, -1.0, 1.0, 0.25);
+    let torus = sdfu::Torus::new(0.25, 1.0);
+
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, -3.0, 0.2),
+        Vec3::new(-0.5, 1.5, -0.5),
+    ];
+    for &p in points.iter() {
+        assert_close(full.dist(p), torus.dist(p));
+    }
+    let w = f32x4::from;
+    assert_lanes_match(
+        frame,
+        BoxFrame::new(Vec3x4::new(w(1.0), w(1.0), w(1.0)), w(0.1)),
+        points,
+    );
+    assert_lanes_match(link, Link::new(w(1.0), w(1.0), w(0.2)), points);
+    assert_lanes_match(
+        half,
+        CappedTorus::new(w(1.0), w(0.0), w(1.0), w(0.25)),
+        points,
+    );
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_triangle_and_quad() {
+    use sdfu::{Dim3D, Quad, Triangle, SDF};
+    use ultraviolet::{f32x4, Vec3, Vec3x4};
+    let (a, b, c) = (Vec3::zero(), Vec3::unit_x(), Vec3::unit_y());
+    let triangle: Triangle<_, Dim3D> = Triangle::new(a, b, c);
+    let flipped: Triangle<_, Dim3D> = Triangle::new(a, c, b);
+    for &(p, d) in [
+        (Vec3::new(0.2, 0.2, 0.5), 0.5),
+        (Vec3::new(0.2, 0.2, -0.5), 0.5),
+        (Vec3::new(-1.0, 0.0, 0.0), 1.0),
+        (Vec3::new(0.5, -1.0, 2.0), 5f32.sqrt()),
+        (Vec3::new(1.0, 1.0, 0.0), 0.5f32.sqrt()),
+    ]
+    .iter()
+    {
+        assert_close(triangle.dist(p), d);
+        assert_close(flipped.dist(p), d);
+    }
+
+    let quad = Quad::new(a, b, Vec3::new(1.0, 1.0, 0.0), c);
+    assert_close(quad.dist(Vec3::new(0.5, 0.5, 1.0)), 1.0);
+    assert_close(quad.dist(Vec3::new(2.0, 2.0, 0.0)), 2f32.sqrt());
+    assert_close(quad.dist(Vec3::new(0.9, 0.9, 0.0)), 0.0);
+
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.2, 0.3, 0.2),
+        Vec3::new(-0.5, 1.5, 0.5),
+    ];
+    let w = |v: Vec3| Vec3x4::new(f32x4::from(v.x), f32x4::from(v.y), f32x4::from(v.z));
+    let wide: Triangle<_, Dim3D> = Triangle::new(w(a), w(b), w(c));
+    assert_lanes_match(triangle, wide, points);
+    let wide = Quad::new(w(a), w(b), w(Vec3::new(1.0, 1.0, 0.0)), w(c));
+    assert_lanes_match(quad, wide, points);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn test_triangle_nalgebra() {
+    use nalgebra::Vector3;
+    use sdfu::{Dim3D, Triangle, SDF};
+    let triangle: Triangle<_, Dim3D> = Triangle::new(Vector3::zeros(), Vector3::x(), Vector3::y());
+    let dist: f32 = triangle.dist(Vector3::new(0.2, 0.2, 0.5));
+    assert!((dist - 0.5).abs() < 1e-6);
+}
+
+/// The distance from the point with radial coordinate `x` and axial coordinate `y` to the
+/// closest of `profile`, a dense sampling of the outline of a shape of revolution.
+#[cfg(feature = "ultraviolet")]
+fn profile_dist(profile: &[(f32, f32)], x: f32, y: f32) -> f32 {
+    profile
+        .iter()
+        .map(|&(px, py)| ((px - x).powi(2) + (py - y).powi(2)).sqrt())
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_cut_spheres() {
+    use sdfu::{Axis, CutHollowSphere, CutSphere, DeathStar, SDF};
+    use std::f32::consts::PI;
+    use ultraviolet::{f32x4, Vec3};
+    let arc = |cx: f32, r: f32, keep: &dyn Fn(f32, f32) -> bool| {
+        (0..=4000)
+            .map(move |i| {
+                let t = PI * i as f32 / 4000.0;
+                (cx + r * t.cos(), r * t.sin())
+            })
+            .filter(|&(a, b)| keep(a, b))
+            .map(|(a, b)| (b, a))
+            .collect::<Vec<_>>()
+    };
+
+    // Profiles as (radial, axial) points around the Y axis.
+    let (r, h) = (1.0f32, 0.3f32);
+    let w = (r * r - h * h).sqrt();
+    let mut cut = arc(0.0, r, &|y, _| y >= h);
+    cut.extend((0..=1000).map(|i| (w * i as f32 / 1000.0, h)));
+    let hollow = arc(0.0, r, &|y, _| y <= h);
+
+    let cut_sphere = CutSphere::new(r, h, Axis::Y);
+    let hollow_sphere = CutHollowSphere::new(r, h, 0.1, Axis::Y);
+    for p in sample_points(200, 2.0) {
+        let (x, y) = ((p.x * p.x + p.z * p.z).sqrt(), p.y);
+        let inside = x * x + y * y < r * r && y > h;
+        let d = profile_dist(&cut, x, y);
+        assert!((cut_sphere.dist(p) - if inside { -d } else { d }).abs() < 2e-3);
+        let d = profile_dist(&hollow, x, y) - 0.1;
+        assert!((hollow_sphere.dist(p) - d).abs() < 2e-3);
+    }
+
+    // Profile as (radial, axial) points around the X axis.
+    let (ra, rb, offset) = (1.0f32, 0.8f32, 1.2f32);
+    let mut star = arc(0.0, ra, &|a, b| (a - offset).powi(2) + b * b >= rb * rb);
+    star.extend(arc(offset, rb, &|a, b| a * a + b * b <= ra * ra));
+    let death_star = DeathStar::new(ra, rb, offset, Axis::X);
+    for p in sample_points(200, 2.0) {
+        let (x, y) = ((p.y * p.y + p.z * p.z).sqrt(), p.x);
+        let inside = x * x + y * y < ra * ra && x * x + (y - offset).powi(2) > rb * rb;
+        let d = profile_dist(&star, x, y);
+        assert!((death_star.dist(p) - if inside { -d } else { d }).abs() < 2e-3);
+    }
+
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.2, 0.9, 0.2),
+        Vec3::new(-0.5, 1.5, 0.5),
+    ];
+    let w = f32x4::from;
+    assert_lanes_match(cut_sphere, CutSphere::new(w(r), w(h), Axis::Y), points);
+    assert_lanes_match(
+        hollow_sphere,
+        CutHollowSphere::new(w(r), w(h), w(0.1), Axis::Y),
+        points,
+    );
+    assert_lanes_match(
+        death_star,
+        DeathStar::new(w(ra), w(rb), w(offset), Axis::X),
+        points,
+    );
+}
+
+/// The exact signed distance from `p` to the polygon with corners `points`.
+#[cfg(feature = "ultraviolet")]
+fn polygon_dist(points: &[ultraviolet::Vec2], p: ultraviolet::Vec2) -> f32 {
+    let mut d = f32::INFINITY;
+    let mut inside = false;
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        let (pa, ba) = (p - a, b - a);
+        let h = (pa.dot(ba) / ba.dot(ba)).clamp(0.0, 1.0);
+        d = d.min((pa - ba * h).mag());
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    if inside {
+        -d
+    } else {
+        d
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+fn assert_lanes_match_2d<S, W>(scalar: S, wide: W)
+where
+    S: sdfu::SDF<f32, ultraviolet::Vec2>,
+    W: sdfu::SDF<ultraviolet::f32x4, ultraviolet::Vec2x4>,
+{
+    use ultraviolet::Vec2;
+    let points = [
+        Vec2::new(0.3, -0.2),
+        Vec2::new(2.0, 0.5),
+        Vec2::new(0.0, -3.0),
+        Vec2::new(-0.5, 1.5),
+    ];
+    let lanes: [f32; 4] = wide.dist(ultraviolet::Vec2x4::from(points)).into();
+    for (&p, &d) in points.iter().zip(lanes.iter()) {
+        assert_close(d, scalar.dist(p));
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_2d_polygons() {
+    use sdfu::{Dim2D, IsoscelesTrapezoid, Parallelogram, Rhombus, Segment, Triangle, SDF};
+    use ultraviolet::{f32x4, Vec2, Vec2x4};
+    let v = Vec2::new;
+    let w = f32x4::from;
+    let wv = |x: f32, y: f32| Vec2x4::new(w(x), w(y));
+
+    let corners = [v(-1.0, -0.5), v(1.5, -1.0), v(0.2, 1.2)];
+    let triangle: Triangle<_, Dim2D> = Triangle::new(corners[0], corners[1], corners[2]);
+    let flipped: Triangle<_, Dim2D> = Triangle::new(corners[0], corners[2], corners[1]);
+    let rhombus = Rhombus::new(v(1.5, 0.75));
+    let trapezoid = IsoscelesTrapezoid::new(1.0, 0.5, 0.75);
+    let parallelogram = Parallelogram::new(1.0, 0.5, 0.4);
+    for p in sample_points_2d(400, 2.5) {
+        assert_close(triangle.dist(p), polygon_dist(&corners, p));
+        assert_close(flipped.dist(p), polygon_dist(&corners, p));
+        assert_close(
+            rhombus.dist(p),
+            polygon_dist(&[v(1.5, 0.0), v(0.0, 0.75), v(-1.5, 0.0), v(0.0, -0.75)], p),
+        );
+        assert_close(
+            trapezoid.dist(p),
+            polygon_dist(
+                &[v(-1.0, -0.75), v(1.0, -0.75), v(0.5, 0.75), v(-0.5, 0.75)],
+                p,
+            ),
+        );
+        assert_close(
+            parallelogram.dist(p),
+            polygon_dist(&[v(-1.4, -0.5), v(0.6, -0.5), v(1.4, 0.5), v(-0.6, 0.5)], p),
+        );
+    }
+
+    let segment = Segment::new(v(-1.0, 0.0), v(1.0, 0.0));
+    assert_close(segment.dist(v(0.0, -0.5)), 0.5);
+    assert_close(segment.dist(v(4.0, 4.0)), 5.0);
+
+    let wide: Triangle<_, Dim2D> = Triangle::new(wv(-1.0, -0.5), wv(1.5, -1.0), wv(0.2, 1.2));
+    assert_lanes_match_2d(triangle, wide);
+    assert_lanes_match_2d(rhombus, Rhombus::new(wv(1.5, 0.75)));
+    assert_lanes_match_2d(trapezoid, IsoscelesTrapezoid::new(w(1.0), w(0.5), w(0.75)));
+    assert_lanes_match_2d(parallelogram, Parallelogram::new(w(1.0), w(0.5), w(0.4)));
+    assert_lanes_match_2d(segment, Segment::new(wv(-1.0, 0.0), wv(1.0, 0.0)));
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_rounded_box() {
+    use sdfu::{RoundedBox, SDF};
+    use ultraviolet::{f32x4, Vec2, Vec2x4};
+    let rounded = RoundedBox::new(Vec2::new(1.0, 0.5), [0.0, 0.1, 0.2, 0.4]);
+    assert_close(rounded.dist(Vec2::new(2.0, 1.5)), 2f32.sqrt());
+    assert_close(rounded.dist(Vec2::new(-2.0, 1.5)), 1.1f32.hypot(1.1) - 0.1);
+    assert_close(rounded.dist(Vec2::new(-2.0, -1.5)), 1.2f32.hypot(1.2) - 0.2);
+    assert_close(rounded.dist(Vec2::new(2.0, -1.5)), 1.4f32.hypot(1.4) - 0.4);
+    assert_close(rounded.dist(Vec2::zero()), -0.5);
+    let w = f32x4::from;
+    let wide = RoundedBox::new(
+        Vec2x4::new(w(1.0), w(0.5)),
+        [w(0.0), w(0.1), w(0.2), w(0.4)],
+    );
+    assert_lanes_match_2d(rounded, wide);
+}
+
+/// The corners of a shape with alternating corners at `outer` and `inner` from the origin,
+/// starting at `start` radians clockwise from the Y axis.
+#[cfg(feature = "ultraviolet")]
+fn star_corners(n: usize, outer: f32, inner: f32, start: f32) -> Vec<ultraviolet::Vec2> {
+    (0..2 * n)
+        .map(|i| {
+            let theta = start + std::f32::consts::PI * i as f32 / n as f32;
+            let r = if i % 2 == 0 { outer } else { inner };
+            ultraviolet::Vec2::new(theta.sin(), theta.cos()) * r
+        })
+        .collect()
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_regular_polygons_and_stars() {
+    use sdfu::{Hexagon, Octagon, Pentagon, RegularPolygon, Star, SDF};
+    use std::f32::consts::PI;
+    use ultraviolet::f32x4;
+    for sides in 3..10 {
+        let polygon = RegularPolygon::new(1.5, sides);
+        let an = PI / sides as f32;
+        // A polygon is a star whose inner corners lie on its sides.
+        let corners = star_corners(sides, 1.5 * an.cos(), 1.5, 0.0);
+        for p in sample_points_2d(400, 2.5) {
+            assert_close(polygon.dist(p), polygon_dist(&corners, p));
+        }
+    }
+
+    for p in sample_points_2d(400, 2.5) {
+        assert_close(
+            Pentagon::new(1.5).dist(p),
+            RegularPolygon::new(1.5, 5).dist(p),
+        );
+        assert_close(
+            Hexagon::new(1.5).dist(p),
+            RegularPolygon::new(1.5, 6).dist(p),
+        );
+        assert_close(
+            Octagon::new(1.5).dist(p),
+            RegularPolygon::new(1.5, 8).dist(p),
+        );
+    }
+
+    for &(points, ratio) in [(5, 0.4), (6, 0.6), (3, 0.2), (8, 0.8)].iter() {
+        let star = Star::new(2.0, points, ratio);
+        let corners = star_corners(points, 2.0, 2.0 * ratio, 0.0);
+        for p in sample_points_2d(400, 2.5) {
+            assert_close(star.dist(p), polygon_dist(&corners, p));
+        }
+    }
+
+    let w = f32x4::from;
+    assert_lanes_match_2d(RegularPolygon::new(1.5, 7), RegularPolygon::new(w(1.5), 7));
+    assert_lanes_match_2d(Star::new(2.0, 5, 0.4), Star::new(w(2.0), 5, w(0.4)));
+    assert_lanes_match_2d(Pentagon::new(1.5), Pentagon::new(w(1.5)));
+    assert_lanes_match_2d(Hexagon::new(1.5), Hexagon::new(w(1.5)));
+    assert_lanes_match_2d(Octagon::new(1.5), Octagon::new(w(1.5)));
+}
+
+#[test]
+#[should_panic]
+fn test_regular_polygon_needs_three_sides() {
+    sdfu::RegularPolygon::new(1.0f32, 2);
+}
+
+#[test]
+#[should_panic]
+fn test_star_needs_two_points() {
+    sdfu::Star::new(1.0f32, 1, 0.5f32);
+}
+
+/// The distance from `p` to a curve, found by densely sampling `curve` over `[0, 1]`.
+#[cfg(feature = "ultraviolet")]
+fn curve_dist(curve: impl Fn(f32) -> ultraviolet::Vec3, p: ultraviolet::Vec3) -> f32 {
+    (0..=20000)
+        .map(|i| (curve(i as f32 / 20000.0) - p).mag())
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_beziers() {
+    use sdfu::{CubicBezier, Line, QuadraticBezier, SDF};
+    use ultraviolet::{f32x4, Vec2, Vec3, Vec3x4};
+    let flat = |v: Vec3| Vec2::new(v.x, v.y);
+    let quadratics = [
+        [
+            Vec3::new(-1.5, -1.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+            Vec3::new(1.5, -1.0, 0.0),
+        ],
+        [
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(1.5, 1.0, 0.0),
+            Vec3::new(-0.5, 1.5, 0.0),
+        ],
+        [
+            Vec3::new(-1.0, 0.5, 0.5),
+            Vec3::new(0.5, -1.0, 1.0),
+            Vec3::new(1.0, 1.0, -1.0),
+        ],
+    ];
+    for [a, b, c] in quadratics {
+        let curve = |t: f32| a * (1.0 - t) * (1.0 - t) + b * 2.0 * t * (1.0 - t) + c * t * t;
+        let sdf = QuadraticBezier::new(a, b, c, 0.25);
+        let sdf_2d = QuadraticBezier::new(flat(a), flat(b), flat(c), 0.25);
+        for p in sample_points(200, 2.0) {
+            assert!((sdf.dist(p) - (curve_dist(curve, p) - 0.25)).abs() < 1e-3);
+            if a.z == 0.0 && b.z == 0.0 && c.z == 0.0 {
+                let d = curve_dist(curve, Vec3::new(p.x, p.y, 0.0)) - 0.25;
+                assert!((sdf_2d.dist(flat(p)) - d).abs() < 1e-3);
+            }
+        }
+    }
+
+    let cubics = [
+        [
+            Vec3::new(-1.5, -1.0, 0.0),
+            Vec3::new(-1.0, 2.0, 0.0),
+            Vec3::new(1.0, -2.0, 0.0),
+            Vec3::new(1.5, 1.0, 0.0),
+        ],
+        // A loop.
+        [
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(2.5, 2.0, 0.0),
+            Vec3::new(-2.5, 2.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ],
+        // A cusp halfway along, where the curve stops and turns back.
+        [
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(-1.0, 1.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ],
+        [
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new(0.0, 2.0, 1.0),
+            Vec3::new(1.0, -1.0, 0.5),
+            Vec3::new(0.5, 1.0, -1.5),
+        ],
+    ];
+    for [a, b, c, d] in cubics {
+        let curve = |t: f32| {
+            let s = 1.0 - t;
+            a * s * s * s + b * 3.0 * s * s * t + c * 3.0 * s * t * t + d * t * t * t
+        };
+        let sdf = CubicBezier::new(a, b, c, d, 0.1);
+        let sdf_2d = CubicBezier::new(flat(a), flat(b), flat(c), flat(d), 0.1);
+        // Also points close to the middle of the curve, such as around the cusp.
+        let middle = curve(0.5);
+        let close = sample_points(200, 0.3).map(|p| p + middle);
+        for p in sample_points(200, 2.0).chain(close) {
+            assert!((sdf.dist(p) - (curve_dist(curve, p) - 0.1)).abs() < 1e-3);
+            if d.z == 0.0 {
+                let expected = curve_dist(curve, Vec3::new(p.x, p.y, 0.0)) - 0.1;
+                assert!((sdf_2d.dist(flat(p)) - expected).abs() < 1e-3);
+            }
+        }
+    }
+
+    // Hairpins with tips so sharp that they fit between the samples the search starts from.
+    // Shifting the control points moves the tips along the curve, through the gaps between
+    // samples, and points just beyond the tips must never be further than the exact distance.
+    for i in 0..=32 {
+        let shift = i as f32 * 0.25 - 4.0;
+        let (a, d) = (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let (b, c) = (
+            Vec3::new(30.0 + shift, 0.2, 0.0),
+            Vec3::new(shift - 30.0, 0.3, 0.0),
+        );
+        let curve = |t: f32| {
+            let s = 1.0 - t;
+            a * s * s * s + b * 3.0 * s * s * t + c * 3.0 * s * t * t + d * t * t * t
+        };
+        let sdf = CubicBezier::new(a, b, c, d, 0.0);
+        let along = (0..=1000).map(|i| curve(i as f32 / 1000.0));
+        let right = along
+            .clone()
+            .fold(Vec3::zero(), |m, q| if q.x > m.x { q } else { m });
+        let left = along.fold(Vec3::zero(), |m, q| if q.x < m.x { q } else { m });
+        for p in [right, left].iter().flat_map(|&tip| {
+            let outward = Vec3::new(tip.x.signum(), 0.0, 0.0);
+            [0.02, 0.05, 0.2].map(|offset| tip + outward * offset)
+        }) {
+            let (d, expected) = (sdf.dist(p), curve_dist(curve, p));
+            assert!(d <= expected + 1e-5 && d >= expected - 2e-3);
+        }
+    }
+
+    // Straight curves are lines.
+    let (a, b) = (Vec3::new(-1.0, 0.5, 0.0), Vec3::new(1.0, -0.5, 0.5));
+    let line = Line::new(a, b, 0.2);
+    let straight = QuadraticBezier::new(a, (a + b) * 0.5, b, 0.2);
+    let straight_cubic = CubicBezier::new(a, (a * 2.0 + b) / 3.0, (a + b * 2.0) / 3.0, b, 0.2);
+    for p in sample_points(200, 2.0) {
+        assert_close(straight.dist(p), line.dist(p));
+        assert!((straight_cubic.dist(p) - line.dist(p)).abs() < 1e-3);
+    }
+
+    let [a, b, c, d] = cubics[2];
+    let w = f32x4::from;
+    let wv = Vec3x4::splat;
+    let points = [
+        Vec3::new(0.3, -0.2, 0.1),
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, -3.0, 0.4),
+        Vec3::new(-0.5, 1.5, 0.0),
+    ];
+    assert_lanes_match(
+        QuadraticBezier::new(a, b, c, 0.1),
+        QuadraticBezier::new(wv(a), wv(b), wv(c), w(0.1)),
+        points,
+    );
+    assert_lanes_match(
+        CubicBezier::new(a, b, c, d, 0.1),
+        CubicBezier::new(wv(a), wv(b), wv(c), wv(d), w(0.1)),
+        points,
+    );
+}
+
+/// Points along the circular arc around `center` from `from` to `to` radians
+/// counter-clockwise from the X axis.
+#[cfg(feature = "ultraviolet")]
+fn arc_points(
+    center: ultraviolet::Vec2,
+    radius: f32,
+    from: f32,
+    to: f32,
+) -> Vec<ultraviolet::Vec2> {
+    (0..=1000)
+        .map(|i| {
+            let theta = from + (to - from) * i as f32 / 1000.0;
+            center + ultraviolet::Vec2::new(theta.cos(), theta.sin()) * radius
+        })
+        .collect()
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_2d_curved_shapes() {
+    use sdfu::{Arc, Egg, Ellipse, Parabola, ParabolaSegment, Pie, Vesica, SDF};
+    use std::f32::consts::{FRAC_PI_2, PI};
+    use ultraviolet::{f32x4, Vec2, Vec2x4};
+    let v = Vec2::new;
+    let w = f32x4::from;
+
+    for aperture in [0.4f32, 1.5, 2.8] {
+        let (sin, cos) = aperture.sin_cos();
+        let pie = Pie::new(sin, cos, 1.5);
+        let mut outline = arc_points(
+            Vec2::zero(),
+            1.5,
+            FRAC_PI_2 - aperture,
+            FRAC_PI_2 + aperture,
+        );
+        outline.push(Vec2::zero());
+        let center_line = arc_points(
+            Vec2::zero(),
+            1.5,
+            FRAC_PI_2 - aperture,
+            FRAC_PI_2 + aperture,
+        );
+        let arc = Arc::new(sin, cos, 1.5, 0.2);
+        for p in sample_points_2d(400, 2.5) {
+            assert_close(pie.dist(p), polygon_dist(&outline, p));
+            let along = center_line
+                .iter()
+                .map(|&c| (p - c).mag())
+                .fold(f32::INFINITY, f32::min);
+            let end = (p - v(1.5 * sin, 1.5 * cos))
+                .mag()
+                .min((p - v(-1.5 * sin, 1.5 * cos)).mag());
+            assert!((arc.dist(p) - (along.min(end) - 0.2)).abs() < 1e-3);
+        }
+        assert_lanes_match_2d(pie, Pie::new(w(sin), w(cos), w(1.5)));
+        assert_lanes_match_2d(arc, Arc::new(w(sin), w(cos), w(1.5), w(0.2)));
+    }
+
+    for (a, b) in [(2.0, 1.0), (0.5, 1.5), (1.2, 1.2), (2.0, 0.4)] {
+        let ellipse = Ellipse::new(v(a, b));
+        let outline: Vec<Vec2> = arc_points(Vec2::zero(), 1.0, 0.0, 2.0 * PI)
+            .into_iter()
+            .map(|p| v(p.x * a, p.y * b))
+            .collect();
+        for p in sample_points_2d(400, 2.5) {
+            assert!((ellipse.dist(p) - polygon_dist(&outline, p)).abs() < 1e-3);
+        }
+        assert_lanes_match_2d(ellipse, Ellipse::new(Vec2x4::new(w(a), w(b))));
+    }
+
+    // Very eccentric ellipses, close to the ends of their axes, where the curvature changes
+    // quickly. The reference is the closest of many points on the ellipse.
+    for (a, b) in [(3.0f32, 0.2f32), (15.0, 1.0), (0.3, 4.5)] {
+        let ellipse = Ellipse::new(v(a, b));
+        let outline: Vec<(f64, f64)> = (0..=200_000)
+            .map(|i| {
+                let (sin, cos) = (i as f64 * std::f64::consts::TAU / 200_000.0).sin_cos();
+                (a as f64 * cos, b as f64 * sin)
+            })
+            .collect();
+        let vertices = [v(a, 0.0), v(-a, 0.0), v(0.0, b), v(0.0, -b)];
+        let near = vertices
+            .iter()
+            .flat_map(|&vertex| sample_points_2d(50, 0.5 * a.min(b)).map(move |o| vertex + o));
+        for p in near.chain([v(-2.991, 0.055)]) {
+            let (x, y) = (p.x as f64, p.y as f64);
+            let closest = outline
+                .iter()
+                .map(|&(cx, cy)| (cx - x) * (cx - x) + (cy - y) * (cy - y))
+                .fold(f64::INFINITY, f64::min)
+                .sqrt();
+            let inside = (x / a as f64).powi(2) + (y / b as f64).powi(2) < 1.0;
+            let expected = if inside { -closest } else { closest };
+            assert!((ellipse.dist(p) as f64 - expected).abs() < 1e-4);
+        }
+    }
+
+    for k in [0.5, 1.0, 3.0] {
+        let parabola = Parabola::new(k);
+        for p in sample_points_2d(400, 2.5) {
+            let curve = (0..=40000)
+                .map(|i| {
+                    let x = i as f32 / 5000.0 - 4.0;
+                    (p - v(x, k * x * x)).mag()
+                })
+                .fold(f32::INFINITY, f32::min);
+            let expected = if p.y > k * p.x * p.x { -curve } else { curve };
+            assert!((parabola.dist(p) - expected).abs() < 1e-3);
+        }
+        assert_lanes_match_2d(parabola, Parabola::new(w(k)));
+    }
+
+    for (width, height) in [(1.0, 2.0), (2.0, 0.5)] {
+        let segment = ParabolaSegment::new(width, height);
+        let outline: Vec<Vec2> = (0..=1000)
+            .map(|i| {
+                let x = width * (i as f32 / 500.0 - 1.0);
+                v(x, height * (1.0 - x * x / (width * width)))
+            })
+            .collect();
+        for p in sample_points_2d(400, 2.5) {
+            assert!((segment.dist(p) - polygon_dist(&outline, p)).abs() < 1e-3);
+        }
+        assert_lanes_match_2d(segment, ParabolaSegment::new(w(width), w(height)));
+    }
+
+    for (width, height) in [(2.0f32, 0.8), (1.0, 0.9)] {
+        let vesica = Vesica::new(width, height);
+        let d = (width * width - height * height) / (2.0 * height);
+        let spread = width.atan2(d);
+        let mut outline = arc_points(
+            v(0.0, -d),
+            d + height,
+            FRAC_PI_2 - spread,
+            FRAC_PI_2 + spread,
+        );
+        outline.extend(arc_points(
+            v(0.0, d),
+            d + height,
+            -FRAC_PI_2 - spread,
+            -FRAC_PI_2 + spread,
+        ));
+        for p in sample_points_2d(400, 2.5) {
+            assert!((vesica.dist(p) - polygon_dist(&outline, p)).abs() < 1e-3);
+        }
+        assert_lanes_match_2d(vesica, Vesica::new(w(width), w(height)));
+    }
+
+    let egg = Egg::new(1.2, 0.3);
+    // The unrounded egg, rounded by offsetting its distance.
+    let r = 0.9;
+    let mut outline = arc_points(Vec2::zero(), r, PI, 2.0 * PI);
+    outline.extend(arc_points(v(-r, 0.0), 2.0 * r, 0.0, PI / 3.0));
+    outline.extend(arc_points(v(r, 0.0), 2.0 * r, 2.0 * PI / 3.0, PI));
+    for p in sample_points_2d(400, 2.5) {
+        assert!((egg.dist(p) - (polygon_dist(&outline, p) - 0.3)).abs() < 1e-3);
+    }
+    assert_lanes_match_2d(egg, Egg::new(w(1.2), w(0.3)));
+}
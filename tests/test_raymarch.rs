@@ -0,0 +1,88 @@
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_march_hit() {
+    use sdfu::raymarch::{MarchOutcome, Raymarcher};
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Sphere::new(1.0);
+    let marcher = Raymarcher::default();
+    let hit = marcher.march(sdf, Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 1.0));
+    assert_eq!(hit.outcome(), MarchOutcome::Hit);
+    assert!((hit.distance - 4.0).abs() < 0.01);
+    assert!((hit.point.z + 1.0).abs() < 0.01);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_march_miss() {
+    use sdfu::raymarch::{Epsilon, MarchOutcome, Raymarcher};
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Sphere::new(1.0);
+    let marcher = Raymarcher::new(128, 10.0, 0.001, Epsilon::Relative);
+    let miss = marcher.march(sdf, Vec3::new(0.0, 2.0, -5.0), Vec3::new(0.0, 0.0, 1.0));
+    assert_eq!(miss.outcome(), MarchOutcome::MaxDistance);
+    let marcher = Raymarcher::new(2, 10.0, 0.001, Epsilon::Absolute);
+    let miss = marcher.march(sdf, Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.6, 0.0, 0.8));
+    assert_eq!(miss.outcome(), MarchOutcome::MaxSteps);
+    assert_eq!(miss.steps, 2);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_march_normals() {
+    use sdfu::raymarch::Raymarcher;
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Sphere::new(1.0).normals(0.001);
+    let hit = Raymarcher::default().march_with_normals(
+        &sdf,
+        Vec3::new(0.0, 0.0, -5.0),
+        Vec3::new(0.0, 0.0, 1.0),
+    );
+    let normal = hit.normal.unwrap();
+    assert!((normal.z + 1.0).abs() < 0.01);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_march_simd_lanes() {
+    use sdfu::raymarch::Raymarcher;
+    use ultraviolet::{f32x4, Vec3x4};
+    let sdf = sdfu::Sphere::new(f32x4::from(1.0));
+    let origin = Vec3x4::new(
+        f32x4::from([0.0, 0.0, 0.0, 3.0]),
+        f32x4::from([0.0, 0.5, 2.0, 0.0]),
+        f32x4::from(-5.0),
+    );
+    let dir = Vec3x4::new(f32x4::from(0.0), f32x4::from(0.0), f32x4::from(1.0));
+    let hit = Raymarcher::default().march(sdf, origin, dir);
+    let hit_lanes: [f32; 4] = hit.hit.into();
+    let far_lanes: [f32; 4] = hit.max_distance.into();
+    assert!(hit_lanes[0] != 0.0 && hit_lanes[1] != 0.0);
+    assert!(hit_lanes[2] == 0.0 && hit_lanes[3] == 0.0);
+    assert!(far_lanes[2] != 0.0 && far_lanes[3] != 0.0);
+    let distances: [f32; 4] = hit.distance.into();
+    assert!((distances[0] - 4.0).abs() < 0.01);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_march_over_relaxed() {
+    use sdfu::raymarch::{Epsilon, MarchOutcome, MarchStrategy, Raymarcher};
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Box::new(Vec3::new(50.0, 0.5, 50.0))
+        .union(sdfu::Sphere::new(0.75).translate(Vec3::new(0.0, 0.5, 0.0)))
+        .round(0.05);
+    let marcher = Raymarcher {
+        strategy: MarchStrategy::OverRelaxed { relaxation: 1.6 },
+        ..Raymarcher::new(512, 100.0, 0.001, Epsilon::Absolute)
+    };
+    let origin = Vec3::new(-40.0, 1.0, 5.0);
+    let dir = Vec3::new(1.0, -0.02, 0.0).normalized();
+    let basic = Raymarcher::new(512, 100.0, 0.001, Epsilon::Absolute).march(sdf, origin, dir);
+    let relaxed = marcher.march(sdf, origin, dir);
+    assert_eq!(basic.outcome(), MarchOutcome::Hit);
+    assert_eq!(relaxed.outcome(), MarchOutcome::Hit);
+    assert!((basic.distance - relaxed.distance).abs() < 0.01);
+    assert!(marcher.steps_saved(sdf, origin, dir) > 0);
+}
@@ -0,0 +1,156 @@
+#[cfg(feature = "ultraviolet")]
+mod common;
+#[cfg(feature = "ultraviolet")]
+use common::sample_points;
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_straight_sweep_is_exact() {
+    use sdfu::sweep::{PathPiece, SweepPath};
+    use sdfu::{Dim2D, Dim3D, SDF};
+    use ultraviolet::{Vec2, Vec3};
+    let path = SweepPath::new(
+        &[PathPiece::Line(
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        )],
+        Vec3::unit_y(),
+        1e-3,
+    );
+    let sdf = sdfu::Box::<Vec2, Dim2D>::new(Vec2::new(0.5, 0.3)).sweep(&path);
+    let reference = sdfu::Box::<Vec3, Dim3D>::new(Vec3::new(0.5, 0.3, 1.0));
+    for p in sample_points(400, 2.0) {
+        assert!((sdf.dist(p) - reference.dist(p)).abs() < 1e-5);
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_corner_is_filled() {
+    use sdfu::sweep::{PathPiece, SweepPath};
+    use sdfu::{Dim2D, SDF};
+    use ultraviolet::{Vec2, Vec3};
+    let corner = Vec3::zero();
+    let path = SweepPath::new(
+        &[
+            PathPiece::Line(Vec3::new(0.0, 0.0, -1.0), corner),
+            PathPiece::Line(corner, Vec3::new(1.0, 0.0, 0.0)),
+        ],
+        Vec3::unit_y(),
+        1e-3,
+    );
+    let sdf = sdfu::Box::<Vec2, Dim2D>::new(Vec2::new(0.2, 0.2)).sweep(&path);
+    // Along the first segment and after the corner, the profile keeps its Y axis up.
+    assert!((sdf.dist(Vec3::new(0.0, 0.5, -0.5)) - 0.3).abs() < 1e-6);
+    assert!((sdf.dist(Vec3::new(0.5, 0.5, 0.0)) - 0.3).abs() < 1e-6);
+    assert!((sdf.dist(Vec3::new(0.5, 0.0, 0.0)) + 0.2).abs() < 1e-6);
+    // Outside the corner, the profile is revolved around it.
+    let r = 0.5f32 * 2f32.sqrt();
+    assert!((sdf.dist(Vec3::new(-0.5, 0.0, 0.5)) - (r - 0.2)).abs() < 1e-6);
+    let d = sdf.dist(Vec3::new(-0.5, 0.5, 0.5));
+    assert!((d - ((r - 0.2).powi(2) + 0.09).sqrt()).abs() < 1e-6);
+    assert!(sdf.dist(Vec3::new(-0.1, 0.1, 0.1)) < 0.0);
+    // The ends are capped flat.
+    assert!((sdf.dist(Vec3::new(0.0, 0.1, -1.5)) - 0.5).abs() < 1e-6);
+    assert!((sdf.dist(Vec3::new(1.5, 0.1, 0.1)) - 0.5).abs() < 1e-6);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_curved_sweep() {
+    use sdfu::sweep::{PathPiece, SweepPath};
+    use sdfu::{Circle, CubicBezier, Dim2D, SDF};
+    use ultraviolet::{Vec2, Vec3};
+    let (a, b, c, d) = (
+        Vec3::new(-1.5, -1.0, 0.0),
+        Vec3::new(-1.0, 1.5, 0.5),
+        Vec3::new(1.0, -1.5, 0.5),
+        Vec3::new(1.5, 1.0, 0.0),
+    );
+    let tolerance = 1e-3;
+    let path = SweepPath::new(
+        &[PathPiece::CubicBezier(a, b, c, d)],
+        Vec3::unit_z(),
+        tolerance,
+    );
+    let tube = SDF::<f32, Vec2>::sweep(Circle::new(0.2), &path);
+    let reference = CubicBezier::new(a, b, c, d, 0.2);
+    let deviations = path.deviations();
+    assert!(deviations.iter().all(|&deviation| deviation <= tolerance));
+    assert!(deviations.iter().any(|&deviation| deviation > 0.0));
+    let curve = |t: f32| {
+        let s = 1.0 - t;
+        a * s * s * s + b * 3.0 * s * s * t + c * 3.0 * s * t * t + d * t * t * t
+    };
+    // Every point on the curve is within the deviation of some segment of the polyline.
+    let polyline = path.points();
+    for i in 0..=10000 {
+        let p = curve(i as f32 / 10000.0);
+        let strays = polyline
+            .windows(2)
+            .zip(deviations)
+            .map(|(pair, &deviation)| {
+                let (pa, ab) = (p - pair[0], pair[1] - pair[0]);
+                (pa - ab * (pa.dot(ab) / ab.dot(ab)).clamp(0.0, 1.0)).mag() - deviation
+            })
+            .fold(f32::INFINITY, f32::min);
+        assert!(strays <= 1e-6);
+    }
+    for p in sample_points(400, 2.0) {
+        // Away from the flat caps, a swept circle is the same as a tube, apart from the
+        // allowance for flattening the curve.
+        let closest = (0..=1000)
+            .map(|i| i as f32 / 1000.0)
+            .min_by(|&s, &t| {
+                let (s, t) = ((curve(s) - p).mag(), (curve(t) - p).mag());
+                s.partial_cmp(&t).unwrap()
+            })
+            .unwrap();
+        if closest > 0.01 && closest < 0.99 {
+            let (d, expected) = (tube.dist(p), reference.dist(p));
+            assert!(d <= expected + 1e-5 && d >= expected - 2.0 * tolerance);
+        }
+    }
+
+    // The distance to a swept box never changes faster than the point moves, except where
+    // the closest segment changes to one with a different deviation, so it is a conservative
+    // bound.
+    let sdf = sdfu::Box::<Vec2, Dim2D>::new(Vec2::new(0.2, 0.1)).sweep(&path);
+    let points: Vec<Vec3> = sample_points(400, 2.0).collect();
+    for pair in points.windows(2) {
+        let (p, q) = (pair[0], pair[0] + (pair[1] - pair[0]) * 0.05);
+        assert!((sdf.dist(p) - sdf.dist(q)).abs() <= (p - q).mag() + tolerance + 1e-4);
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_sweep_deviation_is_local() {
+    use sdfu::sweep::{PathPiece, SweepPath};
+    use sdfu::{Circle, SDF};
+    use ultraviolet::{Vec2, Vec3};
+    // A straight lead-in followed by a curve hundreds of times larger.
+    let start = Vec3::new(0.0, 0.0, -2.0);
+    let path = SweepPath::new(
+        &[
+            PathPiece::Line(start, Vec3::zero()),
+            PathPiece::CubicBezier(
+                Vec3::zero(),
+                Vec3::new(0.0, 0.0, 100.0),
+                Vec3::new(300.0, 0.0, 200.0),
+                Vec3::new(300.0, 0.0, 0.0),
+            ),
+        ],
+        Vec3::unit_y(),
+        1e-2,
+    );
+    assert_eq!(path.deviations()[0], 0.0);
+    assert!(path.deviations().iter().all(|&deviation| deviation <= 1e-2));
+    // Along the line, the pipe is exact no matter how coarsely the curve is flattened.
+    let tube = SDF::<f32, Vec2>::sweep(Circle::new(0.2), &path);
+    for p in sample_points(100, 0.5) {
+        let p = p + Vec3::new(0.0, 0.0, -1.0);
+        let expected = Vec2::new(p.x, p.y).mag() - 0.2;
+        assert!((tube.dist(p) - expected).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(feature = "ultraviolet")]
+mod common;
+#[cfg(feature = "ultraviolet")]
+use common::sample_points_in;
+
+#[cfg(feature = "ultraviolet")]
+fn box_mesh() -> sdfu::meshing::Mesh<ultraviolet::Vec3> {
+    use ultraviolet::Vec3;
+    let sdf = sdfu::Box::new(Vec3::new(1.0, 0.5, 0.75));
+    sdfu::meshing::dual_contouring(
+        sdf,
+        Vec3::broadcast(-1.3),
+        Vec3::broadcast(1.3),
+        [13, 13, 13],
+        &sdfu::util::CentralDifferenceEstimator::new(0.0001),
+    )
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_tetrahedron_distance() {
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let mesh = sdfu::trimesh::TriangleMesh::new(
+        vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        ],
+        vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]],
+    );
+    let sdf = &mesh;
+    // Closest to a face, an edge and a vertex, all outside.
+    assert!((sdf.dist(Vec3::new(0.2, 0.2, -0.5)) - 0.5).abs() < 1e-6);
+    assert!((sdf.dist(Vec3::new(0.5, -1.0, -1.0)) - 2f32.sqrt()).abs() < 1e-6);
+    assert!((sdf.dist(Vec3::new(-1.0, -1.0, -1.0)) - 3f32.sqrt()).abs() < 1e-6);
+    // Inside, closest to the face at x = 0.
+    assert!((sdf.dist(Vec3::new(0.1, 0.2, 0.2)) + 0.1).abs() < 1e-6);
+    // Just outside an edge and a vertex, where the sign needs pseudonormals.
+    assert!(sdf.dist(Vec3::new(0.5, -0.001, -0.001)) > 0.0);
+    assert!(sdf.dist(Vec3::new(-0.001, -0.001, -0.001)) > 0.0);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_matches_analytic() {
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let analytic = sdfu::Box::new(Vec3::new(1.0, 0.5, 0.75));
+    let mesh = sdfu::trimesh::TriangleMesh::from_mesh(&box_mesh());
+    let sdf = &mesh;
+    for p in sample_points_in(400, Vec3::new(2.0, 1.5, 1.7)) {
+        let (a, b) = (analytic.dist(p), sdf.dist(p));
+        assert!((a - b).abs() < 0.05, "{:?}: {} vs {}", p, a, b);
+    }
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_load_obj_and_stl() {
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let source = box_mesh();
+    let direct = sdfu::trimesh::TriangleMesh::from_mesh(&source);
+
+    let mut obj = Vec::new();
+    sdfu::export::write_obj(&source, &mut obj).unwrap();
+    let from_obj = sdfu::trimesh::TriangleMesh::from_obj(&obj[..]).unwrap();
+
+    let mut stl = Vec::new();
+    sdfu::export::write_stl_binary(&source, &mut stl).unwrap();
+    let from_stl = sdfu::trimesh::TriangleMesh::from_stl(&stl[..]).unwrap();
+
+    let mut ascii = Vec::new();
+    sdfu::export::write_stl_ascii(&source, "box", &mut ascii).unwrap();
+    let from_ascii = sdfu::trimesh::TriangleMesh::from_stl(&ascii[..]).unwrap();
+
+    assert_eq!(from_stl.vertices().len(), source.vertices.len());
+    for p in [
+        Vec3::new(0.3, 0.1, 0.2),
+        Vec3::new(1.2, -0.4, 0.0),
+        Vec3::new(-2.0, 1.0, 1.0),
+    ] {
+        let d = (&direct).dist(p);
+        assert!(((&from_obj).dist(p) - d).abs() < 1e-4);
+        assert!(((&from_stl).dist(p) - d).abs() < 1e-4);
+        assert!(((&from_ascii).dist(p) - d).abs() < 1e-4);
+    }
+
+    let bad = sdfu::trimesh::TriangleMesh::<Vec3>::from_obj(&b"v 0 0 0\nf 1 2 3\n"[..]);
+    assert_eq!(bad.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "ultraviolet")]
+#[test]
+fn test_smooth_union_with_primitive() {
+    use sdfu::SDF;
+    use ultraviolet::Vec3;
+    let mesh = sdfu::trimesh::TriangleMesh::from_mesh(&box_mesh());
+    let sdf = (&mesh).union_smooth(
+        sdfu::Sphere::new(0.5).translate(Vec3::new(1.5, 0.0, 0.0)),
+        0.1,
+    );
+    assert!(sdf.dist(Vec3::new(1.5, 0.0, 0.0)) < 0.0);
+    assert!(sdf.dist(Vec3::new(0.0, 0.0, 0.0)) < 0.0);
+    assert!(sdf.dist(Vec3::new(0.0, 2.0, 0.0)) > 0.0);
+}